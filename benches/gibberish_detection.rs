@@ -137,6 +137,11 @@ pub fn bert_detection_benchmark(c: &mut Criterion) {
         "qwertyuiopzxcv",
     ];
     group.bench_function("batch_processing", |b| {
+        b.iter(|| black_box(detector.is_gibberish_batch(black_box(&batch_texts), Sensitivity::Medium)))
+    });
+
+    // Keep a sequential baseline alongside it so the batch speedup is visible.
+    group.bench_function("batch_processing_sequential", |b| {
         b.iter(|| {
             for text in &batch_texts {
                 black_box(detector.is_gibberish(text, Sensitivity::Medium));
@@ -0,0 +1,25 @@
+//! Generates the `ENGLISH_WORDS` phf set from the committed corpus at compile
+//! time, so the library is always in sync with the corpus and no contributor
+//! has to remember to run the `dictionary` binary and commit a generated file.
+
+use std::path::Path;
+
+#[path = "build_support/wordlist.rs"]
+mod wordlist;
+
+fn main() {
+    let corpus_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus").join("english");
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
+    let out_file = Path::new(&out_dir).join("english_words.rs");
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=build_support/wordlist.rs");
+    println!("cargo:rerun-if-changed={}", corpus_dir.display());
+
+    let sources = wordlist::generate(&corpus_dir, &out_file, 0, None)
+        .expect("failed to generate ENGLISH_WORDS from corpus");
+
+    for source in sources {
+        println!("cargo:rerun-if-changed={}", source.display());
+    }
+}
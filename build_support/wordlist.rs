@@ -0,0 +1,265 @@
+//! Shared wordlist code-generation used by both `build.rs` (to emit the
+//! `ENGLISH_WORDS` set into `OUT_DIR` at compile time) and the standalone
+//! `dictionary` binary (to regenerate/inspect the set by hand). It is pulled
+//! into each via `#[path = ...] mod wordlist;` so there is a single source of
+//! truth for how a corpus tree is turned into a `phf_set!`.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8, UTF_16LE, UTF_16BE};
+
+/// File extensions that are never treated as wordlists when walking a corpus.
+pub const EXTENSION_BLACKLIST: &[&str] = &[
+    "rs", "gz", "zip", "bz2", "xz", "tar", "7z", "exe", "bin", "png", "jpg",
+    "jpeg", "gif", "pdf", "o", "so", "rlib",
+];
+
+/// Files smaller than this are skipped as near-empty before any work is done.
+const MIN_FILE_BYTES: u64 = 3;
+
+/// Number of leading bytes read to sniff a BOM-less file's encoding.
+const DETECTION_PREFIX: usize = 64 * 1024;
+
+/// Walk `input` (a file or a directory tree), parse every wordlist found, and
+/// emit both a `phf_set!` named `ENGLISH_WORDS` and a `phf_map!` named
+/// `ENGLISH_WORD_FREQUENCIES` (word -> accumulated count) to `output`. The list
+/// of source files that were read is returned so callers (e.g. `build.rs`) can
+/// emit `cargo:rerun-if-changed=` lines for each.
+///
+/// Files are read line-by-line with a buffered reader so multi-gigabyte
+/// frequency lists don't have to fit in memory, and each word's count is
+/// accumulated (using annotated frequencies where present, otherwise counting
+/// occurrences) so the detector can later weight common words more heavily.
+pub fn generate(
+    input: &Path,
+    output: &Path,
+    csv_column: usize,
+    forced_encoding: Option<&'static Encoding>,
+) -> io::Result<Vec<PathBuf>> {
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    let mut sources: Vec<PathBuf> = Vec::new();
+
+    if input.is_dir() {
+        visit_dir(input, &mut counts, &mut sources, csv_column, forced_encoding)?;
+    } else if input.is_file() {
+        process_file(input, &mut counts, csv_column, forced_encoding)?;
+        sources.push(input.to_path_buf());
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is neither a file nor a directory", input.display()),
+        ));
+    }
+
+    let mut out = File::create(output)?;
+    writeln!(out, "use phf::{{phf_map, phf_set}};\n")?;
+
+    writeln!(out, "pub static ENGLISH_WORDS: phf::Set<&'static str> = phf_set! {{")?;
+    for word in counts.keys() {
+        writeln!(out, "    \"{}\",", escape_literal(word))?;
+    }
+    writeln!(out, "}};")?;
+
+    writeln!(out, "\npub static ENGLISH_WORD_FREQUENCIES: phf::Map<&'static str, u32> = phf_map! {{")?;
+    for (word, count) in &counts {
+        writeln!(out, "    \"{}\" => {}u32,", escape_literal(word), (*count).min(u32::MAX as u64) as u32)?;
+    }
+    writeln!(out, "}};")?;
+
+    Ok(sources)
+}
+
+/// Recursively walk `dir`, processing every non-hidden, non-blacklisted file so
+/// that nested corpus directories are picked up without pre-flattening.
+fn visit_dir(
+    dir: &Path,
+    seen_words: &mut HashMap<String, u64>,
+    sources: &mut Vec<PathBuf>,
+    csv_column: usize,
+    forced_encoding: Option<&'static Encoding>,
+) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if is_hidden(&path) {
+            continue;
+        }
+
+        if path.is_dir() {
+            visit_dir(&path, seen_words, sources, csv_column, forced_encoding)?;
+        } else if path.is_file() && !is_blacklisted(&path) {
+            process_file(&path, seen_words, csv_column, forced_encoding)?;
+            sources.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `path`'s final component is a dotfile.
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy().starts_with('.'))
+        .unwrap_or(false)
+}
+
+/// Whether `path`'s extension is on the non-wordlist blacklist.
+fn is_blacklisted(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| EXTENSION_BLACKLIST.contains(&ext.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+fn process_file(
+    path: &Path,
+    counts: &mut HashMap<String, u64>,
+    csv_column: usize,
+    forced_encoding: Option<&'static Encoding>,
+) -> io::Result<()> {
+    // Skip near-empty files without opening a reader for them.
+    if std::fs::metadata(path)?.len() < MIN_FILE_BYTES {
+        return Ok(());
+    }
+
+    // Resolve the encoding (and any BOM length) from a bounded prefix so we
+    // never have to hold the whole file in memory.
+    let (encoding, bom_length) = resolve_encoding(path, forced_encoding)?;
+
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut raw = Vec::new();
+    let mut first = true;
+    // Read one line's worth of bytes at a time and decode it in isolation.
+    while reader.read_until(b'\n', &mut raw)? != 0 {
+        let slice = if first {
+            first = false;
+            &raw[bom_length.min(raw.len())..]
+        } else {
+            &raw[..]
+        };
+
+        let (line, _, _) = encoding.decode(slice);
+        if let Some((word, freq)) = parse_line(line.trim_end_matches(['\r', '\n']), csv_column) {
+            if is_acceptable(&word) {
+                // Use the annotated frequency when present, otherwise count the
+                // occurrence; accumulate across every input file.
+                let entry = counts.entry(word).or_insert(0);
+                *entry += freq.max(1);
+            }
+        }
+        raw.clear();
+    }
+
+    Ok(())
+}
+
+/// Resolve a file's encoding and BOM length: BOM first, then an explicit
+/// override, then a statistical sniff over a bounded prefix.
+fn resolve_encoding(
+    path: &Path,
+    forced_encoding: Option<&'static Encoding>,
+) -> io::Result<(&'static Encoding, usize)> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut prefix = vec![0u8; DETECTION_PREFIX];
+    let read = std::io::Read::read(&mut reader, &mut prefix)?;
+    prefix.truncate(read);
+
+    let resolved = if prefix.starts_with(&[0xFF, 0xFE]) {
+        (UTF_16LE, 2)
+    } else if prefix.starts_with(&[0xFE, 0xFF]) {
+        (UTF_16BE, 2)
+    } else if prefix.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        (UTF_8, 3)
+    } else if let Some(forced) = forced_encoding {
+        (forced, 0)
+    } else {
+        (detect_encoding(&prefix), 0)
+    };
+
+    Ok(resolved)
+}
+
+/// Statistically sniff the encoding of a BOM-less buffer.
+fn detect_encoding(bytes: &[u8]) -> &'static Encoding {
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    detector.guess(None, true)
+}
+
+/// Parse one wordlist line into a `(word, frequency)` pair, recognizing plain
+/// `word`, frequency-annotated `word<TAB>count` / `word count`, and CSV with a
+/// configurable word column.
+fn parse_line(line: &str, csv_column: usize) -> Option<(String, u64)> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    if line.contains(',') {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let word = fields.get(csv_column)?.to_string();
+        let freq = fields
+            .iter()
+            .rev()
+            .find_map(|field| field.parse::<u64>().ok())
+            .unwrap_or(0);
+        return Some((word, freq));
+    }
+
+    let mut parts = line.split_whitespace();
+    let word = parts.next()?.to_string();
+    let freq = parts.next().and_then(|rest| rest.parse::<u64>().ok()).unwrap_or(0);
+    Some((word, freq))
+}
+
+/// Whether a parsed token is worth keeping in the dictionary.
+fn is_acceptable(word: &str) -> bool {
+    !word.is_empty() && !word.contains(char::is_whitespace) && word.len() > 2
+}
+
+/// Escape a token for emission as a Rust string literal inside `phf_set!` /
+/// `phf_map!`, so a corpus word containing `"` or `\` can't produce generated
+/// code that fails to compile.
+fn escape_literal(word: &str) -> String {
+    word.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_line_reads_frequencies() {
+        assert_eq!(parse_line("the\t500000", 0), Some(("the".to_string(), 500000)));
+        assert_eq!(parse_line("word 42", 0), Some(("word".to_string(), 42)));
+        // A plain word with no count parses with frequency 0 (counted as an
+        // occurrence downstream).
+        assert_eq!(parse_line("plain", 0), Some(("plain".to_string(), 0)));
+        // CSV: the configured column is the word, the last numeric field the count.
+        assert_eq!(parse_line("rank,hello,123", 1), Some(("hello".to_string(), 123)));
+    }
+
+    #[test]
+    fn distinct_frequencies_survive_into_the_map() {
+        let dir = Path::new("target").join("wordlist_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("fixture.txt");
+        let output = dir.join("generated.rs");
+        std::fs::write(&input, "common\t999\nmiddle\t50\nrare\t3\n").unwrap();
+
+        generate(&input, &output, 0, None).unwrap();
+        let emitted = std::fs::read_to_string(&output).unwrap();
+
+        // Each distinct count must reach the emitted frequency map unchanged,
+        // rather than collapsing to a uniform value.
+        assert!(emitted.contains("\"common\" => 999u32,"));
+        assert!(emitted.contains("\"middle\" => 50u32,"));
+        assert!(emitted.contains("\"rare\" => 3u32,"));
+        // And every word lands in the set.
+        assert!(emitted.contains("\"common\","));
+    }
+}
@@ -1,75 +1,54 @@
+//! Thin wrapper around the shared wordlist codegen (see `build_support/wordlist.rs`).
+//!
+//! The library generates `ENGLISH_WORDS` automatically from the committed
+//! corpus via `build.rs`; this binary exists so contributors can regenerate or
+//! inspect the set by hand, optionally against an out-of-tree corpus.
+
 use std::env;
-use std::fs::File;
-use std::io::{self, Write};
-use std::collections::HashSet;
 use std::path::Path;
-use encoding_rs::{UTF_8, UTF_16LE, UTF_16BE};
-
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 3 {
-        eprintln!("Usage: {} <input-file-or-dir> <output-rs-file>", args[0]);
-        std::process::exit(1);
-    }
 
-    let input_path = Path::new(&args[1]);
-    let output_path = &args[2];
-    let mut seen_words = HashSet::new();
-    
-    // Process either a single file or directory
-    if input_path.is_dir() {
-        for entry in std::fs::read_dir(input_path)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_file() && !path.file_name().unwrap().to_string_lossy().starts_with('.') {
-                println!("Processing file: {}", path.display());
-                process_file(&path, &mut seen_words)?;
+use encoding_rs::Encoding;
+
+#[path = "../../build_support/wordlist.rs"]
+mod wordlist;
+
+fn main() {
+    // Pull the optional `--encoding <label>` flag out, leaving positional args.
+    let mut forced_encoding: Option<&'static Encoding> = None;
+    let mut positional: Vec<String> = Vec::new();
+    let mut raw = env::args().skip(1);
+    while let Some(arg) = raw.next() {
+        match arg.as_str() {
+            "--encoding" | "-e" => {
+                let label = raw.next().unwrap_or_default();
+                match Encoding::for_label(label.as_bytes()) {
+                    Some(enc) => forced_encoding = Some(enc),
+                    None => {
+                        eprintln!("Error: unknown encoding label '{}'", label);
+                        std::process::exit(1);
+                    }
+                }
             }
+            other => positional.push(other.to_string()),
         }
-    } else if input_path.is_file() {
-        println!("Processing single file: {}", input_path.display());
-        process_file(input_path, &mut seen_words)?;
-    } else {
-        eprintln!("Error: {} is neither a file nor a directory", input_path.display());
-        std::process::exit(1);
     }
 
-    println!("Total unique words found: {}", seen_words.len());
-
-    // Create output Rust file after processing all inputs
-    let mut output = File::create(output_path)?;
-    writeln!(output, "use phf::phf_set;\n")?;
-    writeln!(output, "pub static ENGLISH_WORDS: phf::Set<&'static str> = phf_set! {{")?;
-
-    for word in seen_words.iter() {
-        writeln!(output, "    \"{}\",", word)?;
+    if positional.len() < 2 || positional.len() > 3 {
+        eprintln!("Usage: dictionary [--encoding <label>] <input-file-or-dir> <output-rs-file> [csv-column]");
+        std::process::exit(1);
     }
 
-    writeln!(output, "}};")?;
-    Ok(())
-}
+    let input_path = Path::new(&positional[0]);
+    let output_path = Path::new(&positional[1]);
+    let csv_column: usize = positional.get(2).and_then(|c| c.parse().ok()).unwrap_or(0);
 
-fn process_file(path: &Path, seen_words: &mut HashSet<String>) -> io::Result<()> {
-    let bytes = std::fs::read(path)?;
-    
-    let (encoding, bom_length) = if bytes.starts_with(&[0xFF, 0xFE]) {
-        (UTF_16LE, 2)
-    } else if bytes.starts_with(&[0xFE, 0xFF]) {
-        (UTF_16BE, 2)
-    } else if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
-        (UTF_8, 3)
-    } else {
-        (UTF_8, 0)
-    };
-
-    let (text, _, _) = encoding.decode(&bytes[bom_length..]);
-    
-    for line in text.lines() {
-        let word = line.trim();
-        if !word.is_empty() && !word.contains(char::is_whitespace) && word.len() > 2 {
-            seen_words.insert(word.to_string());
+    match wordlist::generate(input_path, output_path, csv_column, forced_encoding) {
+        Ok(sources) => {
+            println!("Processed {} file(s) into {}", sources.len(), output_path.display());
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
         }
     }
-    
-    Ok(())
 }
@@ -1,16 +1,35 @@
-use gibberish_or_not::{is_gibberish, Sensitivity};
+use gibberish_or_not::{gibberish_score, is_gibberish, Sensitivity};
 use std::env;
+use std::path::{Path, PathBuf};
 
 fn print_usage(program: &str) {
-    eprintln!("Usage: {} <text> [sensitivity]", program);
+    eprintln!("Usage:");
+    eprintln!("  {} <text> [sensitivity]", program);
+    eprintln!("  {} eval <labeled-dir> [--threshold-sweep]", program);
     eprintln!("  sensitivity: low (strict), medium, high (lenient, default)");
+    eprintln!("  eval: walk <labeled-dir> for *.english.txt / *.gibberish.txt samples");
     std::process::exit(1);
 }
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    if args.len() < 2 || args.len() > 3 {
+    if args.len() < 2 {
+        print_usage(&args[0]);
+    }
+
+    // `eval` subcommand: score the detector against a labeled corpus.
+    if args[1] == "eval" {
+        if args.len() < 3 {
+            print_usage(&args[0]);
+        }
+        let dir = Path::new(&args[2]);
+        let sweep = args.iter().any(|a| a == "--threshold-sweep");
+        run_eval(dir, sweep);
+        return;
+    }
+
+    if args.len() > 3 {
         print_usage(&args[0]);
     }
 
@@ -38,3 +57,182 @@ fn main() {
         println!("This text appears to be valid English");
     }
 }
+
+/// A single labeled sample line: `expected_gibberish` is the ground truth.
+struct Sample {
+    text: String,
+    expected_gibberish: bool,
+}
+
+/// Counts for one sensitivity level, treating "gibberish" as the positive class.
+#[derive(Default)]
+struct Confusion {
+    tp: u32,
+    fp: u32,
+    tn: u32,
+    fn_: u32,
+}
+
+impl Confusion {
+    fn record(&mut self, predicted_gibberish: bool, expected_gibberish: bool) {
+        match (predicted_gibberish, expected_gibberish) {
+            (true, true) => self.tp += 1,
+            (true, false) => self.fp += 1,
+            (false, false) => self.tn += 1,
+            (false, true) => self.fn_ += 1,
+        }
+    }
+
+    fn precision(&self) -> f64 {
+        let denom = self.tp + self.fp;
+        if denom == 0 {
+            0.0
+        } else {
+            self.tp as f64 / denom as f64
+        }
+    }
+
+    fn recall(&self) -> f64 {
+        let denom = self.tp + self.fn_;
+        if denom == 0 {
+            0.0
+        } else {
+            self.tp as f64 / denom as f64
+        }
+    }
+
+    fn f1(&self) -> f64 {
+        let (p, r) = (self.precision(), self.recall());
+        if p + r == 0.0 {
+            0.0
+        } else {
+            2.0 * p * r / (p + r)
+        }
+    }
+}
+
+/// Walk `dir` for labeled samples and report detector quality. By default a
+/// confusion matrix plus precision/recall/F1 is printed for every
+/// [`Sensitivity`] level; with `sweep` the internal gibberish score threshold
+/// is swept instead (see [`run_threshold_sweep`]).
+fn run_eval(dir: &Path, sweep: bool) {
+    let mut samples = Vec::new();
+    if let Err(e) = collect_samples(dir, &mut samples) {
+        eprintln!("Error reading {}: {}", dir.display(), e);
+        std::process::exit(1);
+    }
+
+    if samples.is_empty() {
+        eprintln!(
+            "No samples found under {}. Expected *.english.txt / *.gibberish.txt files.",
+            dir.display()
+        );
+        std::process::exit(1);
+    }
+
+    println!("Evaluated {} samples from {}\n", samples.len(), dir.display());
+
+    if sweep {
+        run_threshold_sweep(&samples);
+        return;
+    }
+
+    for sensitivity in [Sensitivity::Low, Sensitivity::Medium, Sensitivity::High] {
+        let mut confusion = Confusion::default();
+        for sample in &samples {
+            let predicted = is_gibberish(&sample.text, sensitivity);
+            confusion.record(predicted, sample.expected_gibberish);
+        }
+
+        println!("Sensitivity {:?}:", sensitivity);
+        println!("  confusion matrix (positive = gibberish)");
+        println!("                 predicted gibberish   predicted english");
+        println!("  actual gibberish   {:>10}            {:>10}", confusion.tp, confusion.fn_);
+        println!("  actual english     {:>10}            {:>10}", confusion.fp, confusion.tn);
+        println!(
+            "  precision {:.3}  recall {:.3}  F1 {:.3}\n",
+            confusion.precision(),
+            confusion.recall(),
+            confusion.f1()
+        );
+    }
+}
+
+/// Number of steps the threshold sweep takes across the `0.0..=1.0` score range.
+const SWEEP_STEPS: u32 = 20;
+
+/// Sweep the internal gibberish score (`gibberish_score`, `0.0` English ..
+/// `1.0` noise) across its whole range, classifying each sample as gibberish
+/// when its score is at or above the threshold, and print precision/recall/F1
+/// at each step. This surfaces the full precision/recall trade-off so an
+/// operating point can be chosen from data rather than from the three preset
+/// sensitivities.
+fn run_threshold_sweep(samples: &[Sample]) {
+    // Pre-score every sample once; the sweep only varies the cut point.
+    let scored: Vec<(f64, bool)> = samples
+        .iter()
+        .map(|s| (gibberish_score(&s.text), s.expected_gibberish))
+        .collect();
+
+    println!("Threshold sweep over internal gibberish score (positive = gibberish):");
+    println!("  threshold   precision      recall          F1");
+    for step in 0..=SWEEP_STEPS {
+        let threshold = step as f64 / SWEEP_STEPS as f64;
+        let mut confusion = Confusion::default();
+        for &(score, expected) in &scored {
+            confusion.record(score >= threshold, expected);
+        }
+        println!(
+            "  {:>8.3}   {:>9.3}   {:>9.3}   {:>9.3}",
+            threshold,
+            confusion.precision(),
+            confusion.recall(),
+            confusion.f1()
+        );
+    }
+}
+
+/// Recursively gather samples from `*.english.txt` / `*.gibberish.txt` files,
+/// one per non-empty line.
+fn collect_samples(dir: &Path, samples: &mut Vec<Sample>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_samples(&path, samples)?;
+            continue;
+        }
+
+        let expected = match label_of(&path) {
+            Some(expected) => expected,
+            None => continue,
+        };
+
+        let contents = std::fs::read_to_string(&path)?;
+        for line in contents.lines() {
+            let line = line.trim();
+            if !line.is_empty() {
+                samples.push(Sample {
+                    text: line.to_string(),
+                    expected_gibberish: expected,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Ground-truth label for a path: `Some(true)` for gibberish samples,
+/// `Some(false)` for english samples, `None` for files we don't recognize.
+fn label_of(path: &PathBuf) -> Option<bool> {
+    let name = path.file_name()?.to_string_lossy();
+    if name.ends_with(".gibberish.txt") {
+        Some(true)
+    } else if name.ends_with(".english.txt") {
+        Some(false)
+    } else {
+        None
+    }
+}
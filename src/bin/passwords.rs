@@ -1,21 +1,60 @@
+use std::collections::{BTreeSet, HashMap};
 use std::env;
 use std::fs::File;
 use std::io::{self, Write};
-use std::collections::HashSet;
 use std::path::Path;
-use encoding_rs::{UTF_8, UTF_16LE, UTF_16BE};
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE, WINDOWS_1252};
 
+/// Generator for the compiled `PASSWORDS` set.
+///
+/// By default it emits a `phf_set!` of every unique password seen across the
+/// inputs. Two opt-in modes enrich the output:
+///
+/// * `--ranked` additionally emits a `PASSWORD_FREQUENCIES` `phf_map` of each
+///   password to how many times it appeared across all input files, so callers
+///   can weight common leaks more heavily.
+/// * `--diceware` additionally emits a `DICEWARE_WORDS` set of the individual
+///   lowercase word components of hyphen/underscore/space-separated entries, so
+///   passphrases like `correct-horse-battery-staple` can be recognized from
+///   their parts the way diceware generators assemble them.
 fn main() -> io::Result<()> {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 3 {
-        eprintln!("Usage: {} <input-file-or-dir> <output-rs-file>", args[0]);
+
+    let mut ranked = false;
+    let mut diceware = false;
+    let mut forced_encoding: Option<&'static Encoding> = None;
+    let mut positionals: Vec<&str> = Vec::new();
+    let mut iter = args[1..].iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--ranked" => ranked = true,
+            "--diceware" => diceware = true,
+            "--encoding" => {
+                let label = iter.next().unwrap_or_else(|| {
+                    eprintln!("--encoding requires a label (e.g. windows-1251)");
+                    std::process::exit(1);
+                });
+                forced_encoding = Some(Encoding::for_label(label.as_bytes()).unwrap_or_else(|| {
+                    eprintln!("Unknown encoding label: {}", label);
+                    std::process::exit(1);
+                }));
+            }
+            other => positionals.push(other),
+        }
+    }
+
+    if positionals.len() != 2 {
+        eprintln!(
+            "Usage: {} [--ranked] [--diceware] [--encoding <label>] <input-file-or-dir> <output-rs-file>",
+            args[0]
+        );
         std::process::exit(1);
     }
 
-    let input_path = Path::new(&args[1]);
-    let output_path = &args[2];
-    let mut seen_passwords = HashSet::new();
-    
+    let input_path = Path::new(positionals[0]);
+    let output_path = positionals[1];
+    let mut counts: HashMap<String, u32> = HashMap::new();
+
     // Process either a single file or directory
     if input_path.is_dir() {
         for entry in std::fs::read_dir(input_path)? {
@@ -23,53 +62,148 @@ fn main() -> io::Result<()> {
             let path = entry.path();
             if path.is_file() && !path.file_name().unwrap().to_string_lossy().starts_with('.') {
                 println!("Processing file: {}", path.display());
-                process_file(&path, &mut seen_passwords)?;
+                process_file(&path, &mut counts, forced_encoding)?;
             }
         }
     } else if input_path.is_file() {
         println!("Processing single file: {}", input_path.display());
-        process_file(input_path, &mut seen_passwords)?;
+        process_file(input_path, &mut counts, forced_encoding)?;
     } else {
         eprintln!("Error: {} is neither a file nor a directory", input_path.display());
         std::process::exit(1);
     }
 
-    println!("Total unique passwords found: {}", seen_passwords.len());
+    println!("Total unique passwords found: {}", counts.len());
 
     // Create output Rust file after processing all inputs
     let mut output = File::create(output_path)?;
-    writeln!(output, "use phf::phf_set;\n")?;
+    writeln!(output, "use phf::{{phf_map, phf_set}};\n")?;
     writeln!(output, "pub static PASSWORDS: phf::Set<&'static str> = phf_set! {{")?;
+    for password in counts.keys() {
+        writeln!(output, "    \"{}\",", escape_literal(password))?;
+    }
+    writeln!(output, "}};")?;
 
-    for password in seen_passwords.iter() {
-        writeln!(output, "    \"{}\",", password)?;
+    if ranked {
+        // Emit passwords ranked by descending frequency (ties broken
+        // lexicographically for deterministic output).
+        let mut ranked_entries: Vec<(&String, &u32)> = counts.iter().collect();
+        ranked_entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        writeln!(output)?;
+        writeln!(
+            output,
+            "pub static PASSWORD_FREQUENCIES: phf::Map<&'static str, u32> = phf_map! {{"
+        )?;
+        for (password, count) in ranked_entries {
+            writeln!(output, "    \"{}\" => {},", escape_literal(password), count)?;
+        }
+        writeln!(output, "}};")?;
+    }
+
+    if diceware {
+        // Collect the lowercase word components of multi-part entries.
+        let mut words: BTreeSet<String> = BTreeSet::new();
+        for password in counts.keys() {
+            let parts: Vec<&str> = password
+                .split(|c| c == '-' || c == '_' || c == ' ' || c == '.')
+                .filter(|p| !p.is_empty())
+                .collect();
+            if parts.len() >= 2 {
+                for part in parts {
+                    if part.chars().all(|c| c.is_ascii_alphabetic()) {
+                        words.insert(part.to_lowercase());
+                    }
+                }
+            }
+        }
+
+        writeln!(output)?;
+        writeln!(output, "pub static DICEWARE_WORDS: phf::Set<&'static str> = phf_set! {{")?;
+        for word in &words {
+            writeln!(output, "    \"{}\",", escape_literal(word))?;
+        }
+        writeln!(output, "}};")?;
     }
 
-    writeln!(output, "}};")?;
     Ok(())
 }
 
-fn process_file(path: &Path, seen_passwords: &mut HashSet<String>) -> io::Result<()> {
-    let bytes = std::fs::read(path)?;
-    
-    let (encoding, bom_length) = if bytes.starts_with(&[0xFF, 0xFE]) {
-        (UTF_16LE, 2)
+/// Escape a token for emission as a Rust string literal inside `phf_set!` /
+/// `phf_map!`, so a password containing `"` or `\` can't produce generated code
+/// that fails to compile.
+fn escape_literal(password: &str) -> String {
+    password.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Detect the encoding of `bytes`, returning it with the length of any leading
+/// BOM to strip.
+///
+/// A BOM wins outright. Otherwise we look for the NUL-byte signature of
+/// BOM-less UTF-16 (ASCII text in UTF-16LE has NULs at odd offsets, UTF-16BE at
+/// even offsets), then validate UTF-8 continuation-byte structure, and only
+/// fall back to a legacy single-byte encoding (Windows-1252, a Latin-1
+/// superset) when the bytes are not valid UTF-8.
+fn detect_encoding(bytes: &[u8]) -> (&'static Encoding, usize) {
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        return (UTF_16LE, 2);
     } else if bytes.starts_with(&[0xFE, 0xFF]) {
-        (UTF_16BE, 2)
+        return (UTF_16BE, 2);
     } else if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
-        (UTF_8, 3)
-    } else {
+        return (UTF_8, 3);
+    }
+
+    // Sniff a prefix for BOM-less UTF-16 by where the NUL bytes fall.
+    let prefix = &bytes[..bytes.len().min(4096)];
+    let (mut even_nul, mut odd_nul) = (0usize, 0usize);
+    for (i, &b) in prefix.iter().enumerate() {
+        if b == 0 {
+            if i % 2 == 0 {
+                even_nul += 1;
+            } else {
+                odd_nul += 1;
+            }
+        }
+    }
+    let nul_total = even_nul + odd_nul;
+    if nul_total > 0 && nul_total * 4 >= prefix.len() {
+        // Predominantly one-sided NULs indicate UTF-16 of that endianness.
+        return if odd_nul >= even_nul {
+            (UTF_16LE, 0)
+        } else {
+            (UTF_16BE, 0)
+        };
+    }
+
+    if std::str::from_utf8(bytes).is_ok() {
         (UTF_8, 0)
+    } else {
+        (WINDOWS_1252, 0)
+    }
+}
+
+fn process_file(
+    path: &Path,
+    counts: &mut HashMap<String, u32>,
+    forced_encoding: Option<&'static Encoding>,
+) -> io::Result<()> {
+    let bytes = std::fs::read(path)?;
+
+    let (encoding, bom_length) = match forced_encoding {
+        Some(encoding) => (encoding, 0),
+        None => detect_encoding(&bytes),
     };
+    println!("  decoding {} as {}", path.display(), encoding.name());
 
     let (text, _, _) = encoding.decode(&bytes[bom_length..]);
-    
+
     for line in text.lines() {
         let password = line.trim();
         if !password.is_empty() && !password.contains(char::is_whitespace) {
-            seen_passwords.insert(password.to_owned()); // Don't convert case, keep original
+            // Keep a per-entry count across all input files; don't change case.
+            *counts.entry(password.to_owned()).or_insert(0) += 1;
         }
     }
-    
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,169 @@
+//! Typo/OCR-tolerant fuzzy dictionary lookup via bounded edit distance.
+//!
+//! A single transposed or misread letter turns a real word into something
+//! [`crate::is_english_word`] rejects outright. [`is_near_english_word`] accepts
+//! words within a small Levenshtein distance of a dictionary entry.
+//!
+//! To stay fast against the large `ENGLISH_WORDS` set we compile it (once,
+//! lazily) into a trie and walk it with the row-by-row Levenshtein DP: the
+//! current edit-distance row is carried down each edge and any branch whose row
+//! minimum exceeds `max_dist` is pruned, so most of the trie is never visited.
+//!
+//! Whether the detector *folds* near-matches into its English-word count is
+//! gated by [`set_fuzzy_matching`]; it is off by default so the decision
+//! boundary matches the exact-match-only behavior unless explicitly enabled.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+static FUZZY_MATCHING: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable folding fuzzy near-matches into the English-word count
+/// globally. Off by default.
+pub fn set_fuzzy_matching(enabled: bool) {
+    FUZZY_MATCHING.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether fuzzy near-match weighting is currently enabled.
+pub fn fuzzy_matching_enabled() -> bool {
+    FUZZY_MATCHING.load(Ordering::Relaxed)
+}
+
+/// A trie node in the arena-allocated dictionary trie.
+struct Node {
+    children: BTreeMap<char, usize>,
+    is_word: bool,
+}
+
+impl Node {
+    fn new() -> Self {
+        Self {
+            children: BTreeMap::new(),
+            is_word: false,
+        }
+    }
+}
+
+/// Lazily build the dictionary trie from the compiled word set.
+fn trie() -> &'static Vec<Node> {
+    static TRIE: OnceLock<Vec<Node>> = OnceLock::new();
+    TRIE.get_or_init(|| {
+        let mut nodes = vec![Node::new()];
+        for &word in crate::dictionary::ENGLISH_WORDS.iter() {
+            let mut current = 0;
+            for ch in word.chars() {
+                current = match nodes[current].children.get(&ch) {
+                    Some(&next) => next,
+                    None => {
+                        let next = nodes.len();
+                        nodes.push(Node::new());
+                        nodes[current].children.insert(ch, next);
+                        next
+                    }
+                };
+            }
+            nodes[current].is_word = true;
+        }
+        nodes
+    })
+}
+
+/// Whether `word` is within `max_dist` edits of some dictionary word.
+pub fn is_near_english_word(word: &str, max_dist: usize) -> bool {
+    nearest_word(word, max_dist).is_some()
+}
+
+/// The closest dictionary word within `max_dist` edits of `word`, paired with
+/// that distance, or `None` if nothing is close enough.
+pub fn nearest_word(word: &str, max_dist: usize) -> Option<(String, usize)> {
+    let chars: Vec<char> = word.chars().collect();
+    let n = chars.len();
+    let nodes = trie();
+
+    // Row 0: distance from the empty prefix to each prefix of `word`.
+    let first_row: Vec<usize> = (0..=n).collect();
+    let mut best: Option<(String, usize)> = None;
+    let mut prefix = String::new();
+
+    for (&ch, &child) in &nodes[0].children {
+        walk(nodes, child, ch, &chars, &first_row, max_dist, &mut prefix, &mut best);
+    }
+
+    best
+}
+
+#[allow(clippy::too_many_arguments)]
+fn walk(
+    nodes: &[Node],
+    idx: usize,
+    ch: char,
+    word: &[char],
+    prev_row: &[usize],
+    max_dist: usize,
+    prefix: &mut String,
+    best: &mut Option<(String, usize)>,
+) {
+    let n = word.len();
+    let mut row = vec![0usize; n + 1];
+    row[0] = prev_row[0] + 1;
+    for i in 1..=n {
+        let cost = usize::from(word[i - 1] != ch);
+        row[i] = (row[i - 1] + 1).min(prev_row[i] + 1).min(prev_row[i - 1] + cost);
+    }
+
+    prefix.push(ch);
+
+    if nodes[idx].is_word && row[n] <= max_dist {
+        let distance = row[n];
+        if best.as_ref().map_or(true, |(_, d)| distance < *d) {
+            *best = Some((prefix.clone(), distance));
+        }
+    }
+
+    // Only descend while some cell is still within bound.
+    if row.iter().min().copied().unwrap_or(usize::MAX) <= max_dist {
+        for (&next_ch, &child) in &nodes[idx].children {
+            walk(nodes, child, next_ch, word, &row, max_dist, prefix, best);
+        }
+    }
+
+    prefix.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_word_is_distance_zero() {
+        assert_eq!(nearest_word("house", 1), Some(("house".to_string(), 0)));
+    }
+
+    #[test]
+    fn single_edit_typo_is_near() {
+        // One substitution away from "house".
+        assert!(is_near_english_word("housr", 1));
+        // One deletion away from "world".
+        assert!(is_near_english_word("worl", 1));
+    }
+
+    #[test]
+    fn random_string_has_no_near_match() {
+        assert!(!is_near_english_word("xqzvbn", 1));
+        assert_eq!(nearest_word("xqzvbn", 1), None);
+    }
+
+    #[test]
+    fn tighter_bound_rejects_farther_words() {
+        // Two edits from any real word should not match at distance 1.
+        assert!(!is_near_english_word("hxxse", 1));
+    }
+
+    #[test]
+    fn flag_defaults_to_off() {
+        // Folding near-matches into the detector is opt-in; the default must
+        // leave the exact-match-only decision boundary untouched.
+        assert!(!fuzzy_matching_enabled());
+    }
+}
@@ -0,0 +1,336 @@
+//! Natural-language identification via the Cavnar–Trenkle n-gram "out-of-place"
+//! measure.
+//!
+//! For each supported language we build a *profile*: the most frequent
+//! character n-grams (`n = 1..=5`) of some training text, kept as an ordered
+//! list where a gram's index is its rank. At query time we build the same
+//! profile for the input and, for every gram in it, add the absolute
+//! difference between its rank in the input and its rank in the language
+//! profile (a fixed max penalty when the gram is absent). The language with the
+//! smallest total distance is the best candidate; if even that distance is too
+//! large for the requested [`Sensitivity`], the text is treated as gibberish.
+//!
+//! # Relationship to the other language-ID paths
+//!
+//! The crate grew several language-ID entry points with overlapping goals; they
+//! are kept distinct because each answers a different question and they are not
+//! drop-in substitutes:
+//!
+//! * [`detect_language`](crate::detect_language) ranks *all* candidates using
+//!   mixed `n = 1..=5` profiles — the general "what are the most likely
+//!   languages" query.
+//! * [`detect_language_trigram`] (this module) returns the single best match
+//!   from trigram-only profiles with a calibrated confidence; it is the
+//!   cheaper, self-contained path used by the gibberish gate.
+//! * the coverage tables back [`is_language`](crate::is_language), a yes/no
+//!   membership test against one named language.
+//! * [`classify`](crate::classify) folds language ID into the script/gibberish
+//!   [`TextClassification`](crate::TextClassification) summary.
+//!
+//! Consolidating them would mean forcing one profile granularity and one output
+//! shape on all four callers; until a single representation serves every caller
+//! they intentionally remain separate.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::Sensitivity;
+
+/// Number of top n-grams kept in a profile (and the per-gram max penalty).
+const PROFILE_SIZE: usize = 300;
+
+/// Shortest n-gram length considered.
+const MIN_N: usize = 1;
+
+/// Longest n-gram length considered.
+const MAX_N: usize = 5;
+
+/// A natural language the detector can recognize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl Language {
+    /// Every language with a shipped profile.
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::French,
+        Language::German,
+        Language::Spanish,
+    ];
+
+    /// Human-readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "French",
+            Language::German => "German",
+            Language::Spanish => "Spanish",
+        }
+    }
+
+    /// Training text used to build this language's profile. Short by design —
+    /// Cavnar–Trenkle only needs enough text to rank the common grams.
+    fn training_text(self) -> &'static str {
+        match self {
+            Language::English => {
+                "the quick brown fox jumps over the lazy dog. this is a simple \
+                 english sentence containing many of the most common words that \
+                 appear in everyday written and spoken language every day."
+            }
+            Language::French => {
+                "le vif renard brun saute par-dessus le chien paresseux. ceci est \
+                 une phrase simple en français qui contient beaucoup des mots les \
+                 plus courants que l'on rencontre tous les jours."
+            }
+            Language::German => {
+                "der schnelle braune fuchs springt über den faulen hund. dies ist \
+                 ein einfacher deutscher satz mit vielen der häufigsten wörter die \
+                 jeden tag im geschriebenen und gesprochenen gebrauch vorkommen."
+            }
+            Language::Spanish => {
+                "el veloz zorro marrón salta sobre el perro perezoso. esta es una \
+                 frase sencilla en español que contiene muchas de las palabras más \
+                 comunes que aparecen cada día en el lenguaje escrito y hablado."
+            }
+        }
+    }
+}
+
+/// Lazily-built ranked profiles, keyed by language: gram -> rank.
+fn profiles() -> &'static HashMap<Language, HashMap<String, usize>> {
+    static PROFILES: OnceLock<HashMap<Language, HashMap<String, usize>>> = OnceLock::new();
+    PROFILES.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|&lang| {
+                let ranked = ranked_profile(&ordered_profile(lang.training_text()));
+                (lang, ranked)
+            })
+            .collect()
+    })
+}
+
+/// Build the ordered n-gram profile of `text`: grams sorted by descending
+/// frequency (ties broken lexicographically for determinism), truncated to
+/// [`PROFILE_SIZE`].
+fn ordered_profile(text: &str) -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for token in normalize(text).split_whitespace() {
+        // Pad token boundaries with a sentinel so leading/trailing grams matter.
+        let padded = format!("_{}_", token);
+        let chars: Vec<char> = padded.chars().collect();
+        for n in MIN_N..=MAX_N {
+            if chars.len() < n {
+                continue;
+            }
+            for window in chars.windows(n) {
+                *counts.entry(window.iter().collect()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut grams: Vec<(String, usize)> = counts.into_iter().collect();
+    grams.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    grams.truncate(PROFILE_SIZE);
+    grams.into_iter().map(|(gram, _)| gram).collect()
+}
+
+/// Turn an ordered profile into a gram -> rank lookup.
+fn ranked_profile(ordered: &[String]) -> HashMap<String, usize> {
+    ordered
+        .iter()
+        .enumerate()
+        .map(|(rank, gram)| (gram.clone(), rank))
+        .collect()
+}
+
+/// Lowercase and collapse everything that is not a letter into spaces so
+/// n-gramming only sees word-internal letter sequences.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_alphabetic() { c.to_ascii_lowercase() } else { ' ' })
+        .collect()
+}
+
+/// Out-of-place distance between an input profile and a language profile.
+fn out_of_place_distance(input: &[String], lang: &HashMap<String, usize>) -> usize {
+    input
+        .iter()
+        .enumerate()
+        .map(|(input_rank, gram)| match lang.get(gram) {
+            Some(&lang_rank) => input_rank.abs_diff(lang_rank),
+            None => PROFILE_SIZE,
+        })
+        .sum()
+}
+
+/// Rank the supported languages by how closely they match `text`, most likely
+/// first, pairing each with a `0.0..=1.0` confidence derived from its distance.
+///
+/// Returns an empty vector for input with no letters.
+pub fn detect_language(text: &str) -> Vec<(Language, f64)> {
+    let input = ordered_profile(text);
+    if input.is_empty() {
+        return Vec::new();
+    }
+
+    // Worst possible distance for a profile of this size: every gram missing.
+    let max_distance = (input.len() * PROFILE_SIZE) as f64;
+
+    let mut scored: Vec<(Language, f64)> = profiles()
+        .iter()
+        .map(|(&lang, lang_profile)| {
+            let distance = out_of_place_distance(&input, lang_profile) as f64;
+            let confidence = (1.0 - distance / max_distance).max(0.0);
+            (lang, confidence)
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    scored
+}
+
+/// Per-trigram penalty applied when a trigram is absent from a language
+/// profile (also the effective profile length for ranking purposes).
+const TRIGRAM_PENALTY: usize = PROFILE_SIZE;
+
+/// Average per-trigram out-of-place distance above which even the best match is
+/// considered gibberish rather than a real language.
+const MAX_AVG_TRIGRAM_DISTANCE: usize = 220;
+
+/// Lazily-built trigram-only ranked profiles, keyed by language.
+fn trigram_profiles() -> &'static HashMap<Language, HashMap<String, usize>> {
+    static PROFILES: OnceLock<HashMap<Language, HashMap<String, usize>>> = OnceLock::new();
+    PROFILES.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|&lang| {
+                let trigrams = crate::generate_ngrams(lang.training_text(), 3);
+                (lang, ranked_profile(&ranked_by_frequency(trigrams)))
+            })
+            .collect()
+    })
+}
+
+/// Count the grams and return them ordered by descending frequency (ties broken
+/// lexicographically), truncated to [`PROFILE_SIZE`].
+fn ranked_by_frequency(grams: Vec<String>) -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for gram in grams {
+        *counts.entry(gram).or_insert(0) += 1;
+    }
+    let mut ordered: Vec<(String, usize)> = counts.into_iter().collect();
+    ordered.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ordered.truncate(PROFILE_SIZE);
+    ordered.into_iter().map(|(gram, _)| gram).collect()
+}
+
+/// Classify which supported language `text` most resembles using the
+/// Cavnar–Trenkle out-of-place measure over trigrams only, reusing
+/// [`crate::generate_ngrams`].
+///
+/// Returns the single best candidate paired with a `0.0..=1.0` confidence, or
+/// `None` when even the closest language is too distant (i.e. the text is
+/// gibberish).
+pub fn detect_language_trigram(text: &str) -> Option<(Language, f64)> {
+    let input = ranked_profile(&ranked_by_frequency(crate::generate_ngrams(text, 3)));
+    if input.is_empty() {
+        return None;
+    }
+
+    // Put the input back into rank order for distance accumulation.
+    let mut input_ordered: Vec<(&String, &usize)> = input.iter().collect();
+    input_ordered.sort_by_key(|&(_, rank)| *rank);
+
+    let ceiling = input.len() * MAX_AVG_TRIGRAM_DISTANCE;
+
+    let best = trigram_profiles()
+        .iter()
+        .map(|(&lang, profile)| {
+            let distance: usize = input_ordered
+                .iter()
+                .map(|&(gram, &input_rank)| match profile.get(gram) {
+                    Some(&lang_rank) => input_rank.abs_diff(lang_rank),
+                    None => TRIGRAM_PENALTY,
+                })
+                .sum();
+            (lang, distance)
+        })
+        .min_by_key(|&(_, distance)| distance)?;
+
+    if best.1 > ceiling {
+        return None;
+    }
+
+    let confidence = (1.0 - best.1 as f64 / ceiling as f64).clamp(0.0, 1.0);
+    Some((best.0, confidence))
+}
+
+/// Whether `text` most resembles `language` with enough confidence for the
+/// requested sensitivity. Low sensitivity demands the strongest match; High
+/// accepts a weaker one.
+pub fn is_language(text: &str, language: Language, sensitivity: Sensitivity) -> bool {
+    let threshold = match sensitivity {
+        Sensitivity::Low => 0.45,
+        Sensitivity::Medium => 0.35,
+        Sensitivity::High => 0.25,
+    };
+
+    match detect_language(text).first() {
+        Some(&(best, confidence)) => best == language && confidence >= threshold,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_english_text_first() {
+        let ranked = detect_language("the quick brown fox jumps over the lazy dog");
+        assert_eq!(ranked.first().map(|&(lang, _)| lang), Some(Language::English));
+    }
+
+    #[test]
+    fn ranks_french_text_first() {
+        let ranked = detect_language("le renard brun saute par-dessus le chien paresseux");
+        assert_eq!(ranked.first().map(|&(lang, _)| lang), Some(Language::French));
+    }
+
+    #[test]
+    fn empty_input_ranks_nothing() {
+        assert!(detect_language("").is_empty());
+        assert!(detect_language("1234 !!!").is_empty());
+    }
+
+    #[test]
+    fn trigram_path_matches_english() {
+        let best = detect_language_trigram("the common english words appear here every day");
+        assert_eq!(best.map(|(lang, _)| lang), Some(Language::English));
+    }
+
+    #[test]
+    fn trigram_path_rejects_gibberish() {
+        assert_eq!(detect_language_trigram("zxqj wkvb pfgh mnrt"), None);
+    }
+
+    #[test]
+    fn is_language_accepts_and_rejects() {
+        assert!(is_language(
+            "the quick brown fox jumps over the lazy dog",
+            Language::English,
+            Sensitivity::Medium
+        ));
+        assert!(!is_language(
+            "the quick brown fox jumps over the lazy dog",
+            Language::German,
+            Sensitivity::Medium
+        ));
+    }
+}
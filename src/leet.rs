@@ -0,0 +1,114 @@
+//! Leet-speak normalization.
+//!
+//! Inputs like `"h3ll0"`, `"l33t"`, or `"p@ssw0rd"` only fail the dictionary
+//! and password checks because those do exact lookups. [`deleet_variants`]
+//! expands a token into the small set of plausible alphabetic readings (a
+//! bounded cartesian product over ambiguous characters); the dictionary and
+//! password checks consult these variants when normalization is enabled.
+//!
+//! Normalization is on by default; strict callers can opt out with
+//! [`set_leet_normalization`].
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static LEET_NORMALIZATION: AtomicBool = AtomicBool::new(true);
+
+/// Substitution table: a leet character mapped to the letters it can stand for.
+const LEET_SUBS: &[(char, &[char])] = &[
+    ('@', &['a']),
+    ('4', &['a']),
+    ('8', &['b']),
+    ('3', &['e']),
+    ('1', &['i', 'l']),
+    ('!', &['i', 'l']),
+    ('0', &['o']),
+    ('$', &['s']),
+    ('5', &['s']),
+    ('7', &['t']),
+    ('2', &['z']),
+    ('9', &['g']),
+    ('6', &['g']),
+];
+
+/// Upper bound on generated variants, to keep the cartesian product from
+/// blowing up on long, heavily-substituted tokens.
+const MAX_VARIANTS: usize = 64;
+
+/// Enable or disable leet normalization globally.
+pub fn set_leet_normalization(enabled: bool) {
+    LEET_NORMALIZATION.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether leet normalization is currently enabled.
+pub fn leet_normalization_enabled() -> bool {
+    LEET_NORMALIZATION.load(Ordering::Relaxed)
+}
+
+/// Expand `word` into its plausible de-l33ted alphabetic readings.
+///
+/// Non-leet characters are lowercased and kept as-is; ambiguous leet characters
+/// fan out into one variant per candidate letter. The result is capped at
+/// [`MAX_VARIANTS`].
+pub fn deleet_variants(word: &str) -> Vec<String> {
+    let mut variants = vec![String::new()];
+
+    for c in word.chars() {
+        let options: Vec<char> = match LEET_SUBS.iter().find(|(from, _)| *from == c) {
+            Some((_, subs)) => subs.to_vec(),
+            None => vec![c.to_ascii_lowercase()],
+        };
+
+        let mut next = Vec::new();
+        'outer: for base in &variants {
+            for &opt in &options {
+                if next.len() >= MAX_VARIANTS {
+                    break 'outer;
+                }
+                let mut candidate = base.clone();
+                candidate.push(opt);
+                next.push(candidate);
+            }
+        }
+        variants = next;
+    }
+
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_unambiguous_substitutions() {
+        let variants = deleet_variants("h3ll0");
+        assert!(variants.contains(&"hello".to_string()));
+    }
+
+    #[test]
+    fn ambiguous_character_fans_out() {
+        // '1' can read as either 'i' or 'l'.
+        let variants = deleet_variants("l33t");
+        assert!(variants.contains(&"leet".to_string()));
+        let ambiguous = deleet_variants("1");
+        assert!(ambiguous.contains(&"i".to_string()));
+        assert!(ambiguous.contains(&"l".to_string()));
+    }
+
+    #[test]
+    fn plain_word_is_just_lowercased() {
+        assert_eq!(deleet_variants("Hello"), vec!["hello".to_string()]);
+    }
+
+    #[test]
+    fn variant_count_is_capped() {
+        // A long all-ambiguous token must not blow up the cartesian product.
+        let variants = deleet_variants(&"1".repeat(32));
+        assert!(variants.len() <= MAX_VARIANTS);
+    }
+
+    #[test]
+    fn flag_defaults_to_on() {
+        assert!(leet_normalization_enabled());
+    }
+}
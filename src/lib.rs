@@ -1,7 +1,255 @@
 use phf::phf_set;
 
-mod dictionary;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// The English word set, generated at build time from the committed corpus by
+/// `build.rs` (see `build_support/wordlist.rs`) and written to `OUT_DIR`.
+mod dictionary {
+    include!(concat!(env!("OUT_DIR"), "/english_words.rs"));
+}
+mod fuzzy;
+mod language;
+mod leet;
+mod markov;
+mod model;
+mod ngram_scan;
 mod passwords;
+mod script;
+mod strength;
+mod tokenize;
+
+pub use fuzzy::{fuzzy_matching_enabled, is_near_english_word, nearest_word, set_fuzzy_matching};
+pub use language::{detect_language, detect_language_trigram, is_language, Language};
+pub use leet::{deleet_variants, leet_normalization_enabled, set_leet_normalization};
+pub use markov::{gibberish_score, markov_scoring_enabled, set_markov_scoring};
+pub use script::{script_breakdown, Script};
+pub use tokenize::{CjkTokenizer, Tokenizer, WhitespaceTokenizer};
+pub use strength::{password_strength, Strength};
+pub use model::{
+    check_token_status, default_model_path, download_model, download_model_async,
+    download_model_from, download_model_from_async, download_model_with_progress_bar, model_exists,
+    Model, ModelDevice, ModelError, ModelSource, TokenStatus,
+};
+
+/// Gibberish detector that combines the fast heuristic classifier with an
+/// optional transformer model for a second opinion on borderline text.
+///
+/// Construct one with [`GibberishDetector::new`] for heuristic-only detection,
+/// or [`GibberishDetector::with_model`] to consult a downloaded model (see the
+/// `download_model` binary) when the heuristics alone are not decisive.
+#[derive(Clone)]
+pub struct GibberishDetector {
+    model_path: Option<PathBuf>,
+    tokenizer: Arc<dyn Tokenizer>,
+    passwords_as_english: bool,
+}
+
+impl Default for GibberishDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for GibberishDetector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GibberishDetector")
+            .field("model_path", &self.model_path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GibberishDetector {
+    /// Create a detector that relies purely on the heuristic classifier, using
+    /// the default [`WhitespaceTokenizer`].
+    pub fn new() -> Self {
+        Self {
+            model_path: None,
+            tokenizer: Arc::new(WhitespaceTokenizer),
+            passwords_as_english: false,
+        }
+    }
+
+    /// Create a detector that will load the enhanced model from `path` the
+    /// first time it is needed.
+    pub fn with_model<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            model_path: Some(path.into()),
+            tokenizer: Arc::new(WhitespaceTokenizer),
+            passwords_as_english: false,
+        }
+    }
+
+    /// Treat known-password / known-credential strings as non-gibberish. Useful
+    /// for decoders and crackers, where "looks like a leaked password" is a
+    /// positive signal rather than noise. See [`is_known_password`].
+    pub fn treat_passwords_as_english(mut self, enabled: bool) -> Self {
+        self.passwords_as_english = enabled;
+        self
+    }
+
+    /// Use `tokenizer` to find word boundaries before scoring. Pass a
+    /// [`CjkTokenizer`] so predominantly-CJK text is segmented and scored on its
+    /// real-word ratio rather than dismissed as gibberish.
+    pub fn with_tokenizer<T: Tokenizer + 'static>(mut self, tokenizer: T) -> Self {
+        self.tokenizer = Arc::new(tokenizer);
+        self
+    }
+
+    /// Classify `text` as gibberish or not.
+    ///
+    /// The heuristic runs first; text it already rejects stays rejected. Text
+    /// it accepts is, when a model is configured and loadable, confirmed by the
+    /// model so that English-looking noise can still be caught.
+    pub fn is_gibberish(&self, text: &str, sensitivity: Sensitivity) -> bool {
+        // Known leaked credentials are a positive signal for downstream tools.
+        if self.passwords_as_english && is_known_password(text) {
+            return false;
+        }
+
+        // Predominantly-CJK (or other non-space-delimited) text is segmented by
+        // the configured tokenizer and scored on the fraction of tokens that
+        // are real words in the tokenizer's lexicon. With the default
+        // whitespace tokenizer this lexicon is empty, so such text stays
+        // gibberish exactly as before.
+        if let Some(&(Script::Cjk, fraction)) = script::script_breakdown(text).first() {
+            if fraction >= 0.5 {
+                let tokens = self.tokenizer.tokenize(text);
+                if !tokens.is_empty() {
+                    let known = tokens.iter().filter(|t| self.tokenizer.is_known(t)).count();
+                    let ratio = known as f64 / tokens.len() as f64;
+                    let threshold = match sensitivity {
+                        Sensitivity::Low => 0.75,
+                        Sensitivity::Medium => 0.5,
+                        Sensitivity::High => 0.25,
+                    };
+                    return ratio < threshold;
+                }
+            }
+        }
+
+        if is_gibberish(text, sensitivity) {
+            return true;
+        }
+
+        if let Some(model) = self.model() {
+            return model.predict(text);
+        }
+
+        false
+    }
+
+    /// Classify a batch of inputs, amortizing model invocation over the batch.
+    ///
+    /// Each input is first run through the fast heuristic; only inputs the
+    /// heuristic accepts as English *and* that need a model second opinion are
+    /// collected and run through the network as a single padded batch, which is
+    /// far cheaper than one forward pass per input. The result lines up
+    /// one-to-one with `texts`, so empty and heuristic-resolved inputs keep
+    /// their positions.
+    pub fn is_gibberish_batch(&self, texts: &[&str], sensitivity: Sensitivity) -> Vec<bool> {
+        // `Some(true)` already decided gibberish; `None` still needs the model.
+        let mut verdicts: Vec<Option<bool>> = texts
+            .iter()
+            .map(|text| if is_gibberish(text, sensitivity) { Some(true) } else { None })
+            .collect();
+
+        if let Some(model) = self.model() {
+            let pending: Vec<(usize, &str)> = verdicts
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| v.is_none())
+                .map(|(i, _)| (i, texts[i]))
+                .collect();
+
+            if !pending.is_empty() {
+                let batch: Vec<&str> = pending.iter().map(|(_, t)| *t).collect();
+                let predictions = model.predict_batch(&batch);
+                for ((idx, _), prediction) in pending.iter().zip(predictions) {
+                    verdicts[*idx] = Some(prediction);
+                }
+            }
+        }
+
+        // Inputs the heuristic accepted with no model configured are English.
+        verdicts.into_iter().map(|v| v.unwrap_or(false)).collect()
+    }
+
+    /// Rank the supported natural languages `text` most resembles, most likely
+    /// first. See [`detect_language`].
+    pub fn detect_language(&self, text: &str) -> Vec<(Language, f64)> {
+        detect_language(text)
+    }
+
+    /// Classify `text` as English, a different supported language, or gibberish.
+    ///
+    /// English text (per the heuristic at this sensitivity) reports
+    /// [`TextClassification::English`]. Otherwise the trigram language profiles
+    /// are consulted: a confident non-English match reports
+    /// [`TextClassification::OtherLanguage`] so downstream pipelines keep
+    /// legitimate French/Spanish/German text instead of discarding it; anything
+    /// else is [`TextClassification::Gibberish`].
+    pub fn classify(&self, text: &str, sensitivity: Sensitivity) -> TextClassification {
+        if !self.is_gibberish(text, sensitivity) {
+            return TextClassification::English;
+        }
+
+        // Cheap trigram out-of-place profile: if the closest language is a
+        // confident non-English match, keep the text as that language.
+        if let Some((lang, confidence)) = detect_language_trigram(text) {
+            let threshold = match sensitivity {
+                Sensitivity::Low => 0.35,
+                Sensitivity::Medium => 0.45,
+                Sensitivity::High => 0.55,
+            };
+            if lang != Language::English && confidence >= threshold {
+                return TextClassification::OtherLanguage {
+                    lang,
+                    confidence: confidence as f32,
+                };
+            }
+        }
+
+        TextClassification::Gibberish
+    }
+
+    /// Whether `text` most resembles `language` at the given sensitivity.
+    pub fn is_language(&self, text: &str, language: Language, sensitivity: Sensitivity) -> bool {
+        is_language(text, language, sensitivity)
+    }
+
+    /// Load the configured model, if any and if it loads successfully.
+    fn model(&self) -> Option<&'static Model> {
+        self.model_path.as_deref().and_then(Model::get_or_load)
+    }
+}
+
+/// The outcome of [`GibberishDetector::classify`]: what a string *is*, rather
+/// than only whether it is English gibberish.
+///
+/// This is distinct from the lower-level [`Classification`] (which reports a
+/// Unicode script breakdown) and from the raw language-ID functions
+/// ([`detect_language`], [`is_language`]): `TextClassification` is the
+/// detector-level verdict that folds the trigram language match
+/// ([`detect_language_trigram`]) together with the English/gibberish decision.
+/// These four language-ID paths overlap in purpose but answer different
+/// questions (rank-all vs single-best vs membership vs folded verdict) and are
+/// intentionally kept separate rather than consolidated.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextClassification {
+    /// Recognized as English.
+    English,
+    /// Not English and not a confident match for any other known language.
+    Gibberish,
+    /// Recognized as a different supported language, with the trigram-profile
+    /// confidence of that guess.
+    OtherLanguage {
+        /// The best-matching language.
+        lang: Language,
+        /// Confidence of the match, `0.0..=1.0`.
+        confidence: f32,
+    },
+}
 
 /// Sensitivity level for gibberish detection
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -22,7 +270,22 @@ pub enum Sensitivity {
 }
 
 fn is_english_word(word: &str) -> bool {
-    dictionary::ENGLISH_WORDS.contains(word)
+    if dictionary::ENGLISH_WORDS.contains(word) {
+        return true;
+    }
+
+    // Fall back to de-l33ted readings so "h3ll0" reads as "hello".
+    leet::leet_normalization_enabled()
+        && leet::deleet_variants(word)
+            .iter()
+            .any(|variant| dictionary::ENGLISH_WORDS.contains(variant.as_str()))
+}
+
+/// Returns the accumulated corpus frequency/count of `word`, if it is a known
+/// English word. Callers can use this to weight common words more heavily than
+/// rare ones when scoring a string.
+pub fn word_frequency(word: &str) -> Option<u32> {
+    dictionary::ENGLISH_WORD_FREQUENCIES.get(word).copied()
 }
 
 /// Checks if the given text matches a known common password.
@@ -55,7 +318,35 @@ fn is_english_word(word: &str) -> bool {
 /// assert!(!is_password("not-a-common-password"));
 /// ```
 pub fn is_password(text: &str) -> bool {
-    passwords::PASSWORDS.contains(text)
+    if passwords::PASSWORDS.contains(text) {
+        return true;
+    }
+
+    // Also match l33t-obfuscated common passwords, e.g. "p@ssw0rd".
+    leet::leet_normalization_enabled()
+        && leet::deleet_variants(text)
+            .iter()
+            .any(|variant| passwords::PASSWORDS.contains(variant.as_str()))
+}
+
+/// Whether `s` looks like a known leaked credential.
+///
+/// A superset of [`is_password`]: in addition to exact (and l33t) matches in the
+/// compiled password set, this recognizes diceware-style passphrases such as
+/// `correct-horse-battery-staple` — three or more components, each a known word
+/// or password, joined by common separators. Tools built on this crate treat a
+/// positive result as signal ("looks like a leaked password") rather than noise.
+pub fn is_known_password(s: &str) -> bool {
+    if is_password(s) {
+        return true;
+    }
+
+    let parts: Vec<&str> = s
+        .split(|c| c == '-' || c == '_' || c == ' ' || c == '.')
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    parts.len() >= 3 && parts.iter().all(|p| is_english_word(p) || is_password(p))
 }
 // The dictionary module provides a perfect hash table implementation
 // using the phf crate, which is generated at compile time
@@ -83,6 +374,132 @@ pub fn is_password(text: &str) -> bool {
 ///    - 0 English words → more lenient n-gram check
 /// 4. Use different n-gram thresholds depending on sensitivity level
 pub fn is_gibberish(text: &str, sensitivity: Sensitivity) -> bool {
+    classify(text, sensitivity).is_gibberish
+}
+
+/// Classify a batch of inputs with the heuristic detector, returning one verdict
+/// per input in order. Equivalent to mapping [`is_gibberish`] over `texts`; the
+/// batch form mirrors [`GibberishDetector::is_gibberish_batch`] for callers that
+/// do not use a model.
+pub fn is_gibberish_batch(texts: &[&str], sensitivity: Sensitivity) -> Vec<bool> {
+    texts.iter().map(|text| is_gibberish(text, sensitivity)).collect()
+}
+
+/// A structured classification of a piece of text.
+///
+/// Richer than the bare `bool` from [`is_gibberish`]: it reports which Unicode
+/// scripts the text is written in (and in what proportion), the trigram and
+/// quadgram membership scores the Latin heuristic computed, and the final
+/// gibberish verdict. This lets callers distinguish "valid text in a script we
+/// do not model" (e.g. CJK) from "actual random bytes".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification {
+    /// Scripts present in the text with their character fractions, most
+    /// frequent first. See [`script_breakdown`].
+    pub scripts: Vec<(Script, f64)>,
+    /// Common-trigram membership ratio of the cleaned text.
+    pub trigram_score: f64,
+    /// Common-quadgram membership ratio of the cleaned text.
+    pub quadgram_score: f64,
+    /// Whether the text is classified as gibberish at the given sensitivity.
+    pub is_gibberish: bool,
+}
+
+/// Classify `text`, returning its script breakdown, n-gram scores, and gibberish
+/// verdict. [`is_gibberish`] is the `bool`-returning wrapper over this.
+pub fn classify(text: &str, sensitivity: Sensitivity) -> Classification {
+    let cleaned = clean_text(text);
+    let (trigram_score, quadgram_score) = ngram_scan::english_ngram_scores(&cleaned);
+    Classification {
+        scripts: script::script_breakdown(text),
+        trigram_score,
+        quadgram_score,
+        is_gibberish: is_gibberish_lang(text, Language::English, sensitivity),
+    }
+}
+
+/// Checks if `text` is gibberish *with respect to a specific language*.
+///
+/// For [`Language::English`] this runs the full heuristic classifier; for other
+/// languages it scores trigram/quadgram coverage against that language's tables
+/// (seeded from the multilingual pangram corpus) and classifies as gibberish
+/// when coverage falls below the sensitivity-scaled threshold. [`is_gibberish`]
+/// is the English-only wrapper over this function.
+pub fn is_gibberish_lang(text: &str, lang: Language, sensitivity: Sensitivity) -> bool {
+    if lang == Language::English {
+        return is_gibberish_english(text, sensitivity);
+    }
+
+    let cleaned = clean_text(text);
+    if cleaned.len() < 10 {
+        return true;
+    }
+
+    let (trigram_score, quadgram_score) = language_ngram_coverage(&cleaned, lang);
+    let threshold = match sensitivity {
+        Sensitivity::Low => 0.30,
+        Sensitivity::Medium => 0.20,
+        Sensitivity::High => 0.12,
+    };
+
+    trigram_score < threshold && quadgram_score < threshold
+}
+
+/// Select the language whose n-gram model best covers `text`, above the
+/// sensitivity threshold; `None` when nothing fits (i.e. gibberish).
+pub fn detect_language_coverage(text: &str, sensitivity: Sensitivity) -> Option<Language> {
+    let cleaned = clean_text(text);
+    if cleaned.len() < 10 {
+        return None;
+    }
+
+    let threshold = match sensitivity {
+        Sensitivity::Low => 0.30,
+        Sensitivity::Medium => 0.20,
+        Sensitivity::High => 0.12,
+    };
+
+    Language::ALL
+        .iter()
+        .map(|&lang| {
+            let (tri, quad) = language_ngram_coverage(&cleaned, lang);
+            (lang, tri.max(quad))
+        })
+        .filter(|&(_, coverage)| coverage >= threshold)
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(lang, _)| lang)
+}
+
+/// Trigram and quadgram coverage of `cleaned` against `lang`'s common n-gram
+/// tables, computed the same way the English path does.
+fn language_ngram_coverage(cleaned: &str, lang: Language) -> (f64, f64) {
+    let (trigrams_set, quadgrams_set) = match lang {
+        Language::English => (&COMMON_TRIGRAMS, &COMMON_QUADGRAMS),
+        Language::French => (&FRENCH_TRIGRAMS, &FRENCH_QUADGRAMS),
+        Language::German => (&GERMAN_TRIGRAMS, &GERMAN_QUADGRAMS),
+        Language::Spanish => (&SPANISH_TRIGRAMS, &SPANISH_QUADGRAMS),
+    };
+
+    let trigrams = generate_ngrams(cleaned, 3);
+    let quadgrams = generate_ngrams(cleaned, 4);
+
+    let trigram_score = if trigrams.is_empty() {
+        0.0
+    } else {
+        trigrams.iter().filter(|g| trigrams_set.contains(g.as_str())).count() as f64
+            / trigrams.len() as f64
+    };
+    let quadgram_score = if quadgrams.is_empty() {
+        0.0
+    } else {
+        quadgrams.iter().filter(|g| quadgrams_set.contains(g.as_str())).count() as f64
+            / quadgrams.len() as f64
+    };
+
+    (trigram_score, quadgram_score)
+}
+
+fn is_gibberish_english(text: &str, sensitivity: Sensitivity) -> bool {
     // Clean the text first
     let cleaned = clean_text(text);
 
@@ -91,6 +508,21 @@ pub fn is_gibberish(text: &str, sensitivity: Sensitivity) -> bool {
         return true;
     }
 
+    // Opt-in continuous Markov scoring: threshold the smooth gibberish score
+    // against a sensitivity-calibrated cutoff. Input too short for the model
+    // order falls through to the established heuristic below.
+    if markov::markov_scoring_enabled() {
+        if let Some(mean) = markov::mean_log_prob(&cleaned) {
+            let score = markov::score_from_mean(mean);
+            let cutoff = match sensitivity {
+                Sensitivity::Low => 0.75,
+                Sensitivity::Medium => 0.60,
+                Sensitivity::High => 0.45,
+            };
+            return score > cutoff;
+        }
+    }
+
     // For very short cleaned text, only check if it's an English word
     if cleaned.len() < 10 {
         let is_english = is_english_word(&cleaned);
@@ -106,12 +538,40 @@ pub fn is_gibberish(text: &str, sensitivity: Sensitivity) -> bool {
     // Count English words
     let english_words: Vec<&&str> = words.iter().filter(|w| is_english_word(w)).collect();
     let english_word_count = english_words.len();
+
+    // Count lightly-corrupted (near-) English words so OCR/typo garbling isn't
+    // misclassified. Near-matches are capped and weighted below exact matches;
+    // random strings find no close neighbors in the trie. This is opt-in (see
+    // [`fuzzy::set_fuzzy_matching`]); when disabled the count is zero and the
+    // ratio matches the exact-match-only behavior.
+    let near_match_count = if fuzzy::fuzzy_matching_enabled() {
+        words
+            .iter()
+            .filter(|w| w.len() >= 3 && !is_english_word(w) && fuzzy::is_near_english_word(w, 1))
+            .take(MAX_NEAR_MATCHES)
+            .count()
+    } else {
+        0
+    };
+    let effective_english = english_word_count as f64 + near_match_count as f64 * NEAR_MATCH_WEIGHT;
+
     let english_word_ratio = if words.is_empty() {
         0.0
     } else {
-        english_word_count as f64 / words.len() as f64
+        effective_english / words.len() as f64
     };
 
+    // Space-free text (run-together words) has no word boundaries to count, so
+    // recover them by segmentation: a strong full dictionary segmentation means
+    // the input is English rather than gibberish.
+    if words.len() <= 1 {
+        if let Some((segmented, avg_logprob)) = segment(&cleaned) {
+            if segmented.len() >= 2 && avg_logprob > -7.0 {
+                return false;
+            }
+        }
+    }
+
     // Check for non-printable characters which are strong indicators of gibberish
     let non_printable_count = text
         .chars()
@@ -132,32 +592,11 @@ pub fn is_gibberish(text: &str, sensitivity: Sensitivity) -> bool {
     // Calculate vowel-consonant ratio - English has a fairly consistent ratio
     let vowel_consonant_ratio = calculate_vowel_consonant_ratio(&cleaned);
 
-    // Proceed with trigram/quadgram analysis (but with less weight)
-    let trigrams = generate_ngrams(&cleaned, 3);
-    let quadgrams = generate_ngrams(&cleaned, 4);
-
-    let valid_trigrams = trigrams
-        .iter()
-        .filter(|gram| COMMON_TRIGRAMS.contains(gram.as_str()))
-        .collect::<Vec<_>>();
-
-    let valid_quadgrams = quadgrams
-        .iter()
-        .filter(|gram| COMMON_QUADGRAMS.contains(gram.as_str()))
-        .collect::<Vec<_>>();
-
-    // Calculate scores
-    let trigram_score = if trigrams.is_empty() {
-        0.0
-    } else {
-        valid_trigrams.len() as f64 / trigrams.len() as f64
-    };
-
-    let quadgram_score = if quadgrams.is_empty() {
-        0.0
-    } else {
-        valid_quadgrams.len() as f64 / quadgrams.len() as f64
-    };
+    // Proceed with trigram/quadgram analysis (but with less weight). The
+    // single-pass Aho-Corasick scanner produces the same membership ratios as
+    // filtering `generate_ngrams` against the common-gram sets, without the
+    // temporary `Vec<String>` allocations (see the `ngram_scan` module).
+    let (trigram_score, quadgram_score) = ngram_scan::english_ngram_scores(&cleaned);
 
     // Calculate a composite score that combines multiple metrics
     // This makes the algorithm more robust than relying heavily on n-grams
@@ -171,7 +610,12 @@ pub fn is_gibberish(text: &str, sensitivity: Sensitivity) -> bool {
 
     // N-gram scores have lower weight
     composite_score += trigram_score * 0.15;
-    composite_score += quadgram_score * 0.1;
+
+    // The Markov quadgram log-probability augments the coarse membership ratio,
+    // giving smoother discrimination on borderline input.
+    let quadgram_log_prob = quadgram_log_probability(&cleaned);
+    composite_score += quadgram_score * 0.05;
+    composite_score += quadgram_english_score(quadgram_log_prob) * 0.05;
 
     // Vowel-consonant ratio has low weight
     composite_score += if (0.3..=0.7).contains(&vowel_consonant_ratio) {
@@ -226,6 +670,118 @@ pub fn is_gibberish(text: &str, sensitivity: Sensitivity) -> bool {
     composite_score < threshold
 }
 
+/// Average per-quadgram log10-probability of `text` under the English quadgram
+/// model in [`ENGLISH_QUADGRAM_COUNTS`].
+///
+/// Each quadgram contributes `log10(count / total)`, with a floor of
+/// `log10(0.01 / total)` for quadgrams the model has never seen. English text
+/// clusters tightly just above a known mean while gibberish falls far below,
+/// which discriminates the borderline cases far better than binary membership.
+pub fn quadgram_log_probability(text: &str) -> f64 {
+    let floor = (0.01 / ENGLISH_QUADGRAM_TOTAL).log10();
+    let quadgrams = generate_ngrams(text, 4);
+    if quadgrams.is_empty() {
+        return floor;
+    }
+
+    let sum: f64 = quadgrams
+        .iter()
+        .map(|gram| match ENGLISH_QUADGRAM_COUNTS.get(gram.as_str()) {
+            Some(&count) => (count as f64 / ENGLISH_QUADGRAM_TOTAL).log10(),
+            None => floor,
+        })
+        .sum();
+
+    sum / quadgrams.len() as f64
+}
+
+/// Map the average quadgram log-probability into a `0.0..=1.0` "English-ness"
+/// signal, anchored between the unseen-quadgram floor and a typical English
+/// mean (roughly `-2.5`).
+fn quadgram_english_score(log_prob: f64) -> f64 {
+    let floor = (0.01 / ENGLISH_QUADGRAM_TOTAL).log10();
+    const ENGLISH_MEAN: f64 = -2.5;
+    ((log_prob - floor) / (ENGLISH_MEAN - floor)).clamp(0.0, 1.0)
+}
+
+/// Maximum number of near-dictionary matches counted toward the English-word
+/// ratio, and the weight each such match contributes relative to an exact hit.
+const MAX_NEAR_MATCHES: usize = 8;
+const NEAR_MATCH_WEIGHT: f64 = 0.5;
+
+/// Maximum word length considered when segmenting space-free text.
+const MAX_WORD_LEN: usize = 20;
+
+/// Nominal corpus size used to turn a word count into a unigram probability.
+const WORD_FREQ_TOTAL: f64 = 1.0e9;
+
+/// Unigram log10-probability of `word`: `log10(count / total)` for known words,
+/// and a length-scaled penalty (Norvig's `10 / (N * 10^len)`) for unknown ones
+/// so longer unknown runs are punished harder.
+fn word_logprob(word: &str) -> f64 {
+    match word_frequency(word) {
+        Some(count) if count > 0 => (count as f64 / WORD_FREQ_TOTAL).log10(),
+        _ => (10.0 / (WORD_FREQ_TOTAL * 10f64.powi(word.len() as i32))).log10(),
+    }
+}
+
+/// Recover the most probable word boundaries of run-together text like
+/// `"HelloSkeletonsThisIsATest"` via dynamic programming.
+///
+/// `best[i]` holds the best total log-probability of segmenting the first `i`
+/// letters; for each `i` we try every split point `j` (bounded by
+/// [`MAX_WORD_LEN`]) where `text[j..i]` is a known English word. The result is
+/// the recovered word list paired with the average per-word log-probability, or
+/// `None` when no full dictionary segmentation exists.
+pub fn segment(text: &str) -> Option<(Vec<String>, f64)> {
+    let chars: Vec<char> = text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let n = chars.len();
+    if n == 0 {
+        return None;
+    }
+
+    let mut best = vec![f64::NEG_INFINITY; n + 1];
+    let mut back = vec![0usize; n + 1];
+    best[0] = 0.0;
+
+    for i in 1..=n {
+        let start = i.saturating_sub(MAX_WORD_LEN);
+        for j in start..i {
+            if best[j] == f64::NEG_INFINITY {
+                continue;
+            }
+            let word: String = chars[j..i].iter().collect();
+            if is_english_word(&word) {
+                let score = best[j] + word_logprob(&word);
+                if score > best[i] {
+                    best[i] = score;
+                    back[i] = j;
+                }
+            }
+        }
+    }
+
+    if best[n] == f64::NEG_INFINITY {
+        return None;
+    }
+
+    let mut words = Vec::new();
+    let mut i = n;
+    while i > 0 {
+        let j = back[i];
+        words.push(chars[j..i].iter().collect());
+        i = j;
+    }
+    words.reverse();
+
+    let avg = best[n] / words.len() as f64;
+    Some((words, avg))
+}
+
 /// Calculate character entropy - a measure of randomness in the text
 fn calculate_entropy(text: &str) -> f64 {
     let text = text.to_lowercase();
@@ -305,6 +861,25 @@ static COMMON_CHAR_PAIRS: phf::Set<&'static str> = phf_set! {
     "ra", "ce", "li", "ch", "ll", "be", "ma", "si", "om", "ur"
 };
 
+/// Nominal corpus total used to turn a count in [`ENGLISH_QUADGRAM_COUNTS`]
+/// into a probability. It is larger than the sum of the listed counts
+/// (4,312,282), since the table holds only a sample of all quadgrams.
+const ENGLISH_QUADGRAM_TOTAL: f64 = 4_500_000.0;
+
+/// Relative counts of common English quadgrams, sampled from a large corpus.
+/// Backs [`quadgram_log_probability`]: the Markov successor to the coarse
+/// membership test in [`COMMON_QUADGRAMS`].
+static ENGLISH_QUADGRAM_COUNTS: phf::Map<&'static str, u32> = phf::phf_map! {
+    "tion" => 433_177, "atio" => 319_258, "that" => 306_957, "ther" => 290_439,
+    "with" => 276_459, "ment" => 183_832, "ions" => 175_788, "this" => 166_364,
+    "here" => 150_837, "from" => 142_867, "ould" => 128_631, "ting" => 127_086,
+    "hich" => 115_862, "whic" => 114_986, "ctio" => 103_420, "ever" => 94_624,
+    "they" => 92_678, "thin" => 91_446, "have" => 89_756, "othe" => 85_305,
+    "were" => 83_722, "tive" => 80_951, "ough" => 77_705, "ight" => 76_556,
+    "ence" => 72_046, "ally" => 70_831, "ning" => 68_186, "ande" => 66_021,
+    "also" => 60_030, "come" => 58_344, "ntha" => 55_210, "sion" => 52_908,
+};
+
 static COMMON_QUADGRAMS: phf::Set<&'static str> = phf_set! {
     "tion", "atio", "that", "ther", "with", "ment", "ions", "this",
     "here", "from", "ould", "ting", "hich", "whic", "ctio", "ever",
@@ -319,6 +894,38 @@ static COMMON_TRIGRAMS: phf::Set<&'static str> = phf_set! {
     "ica", "ist", "ear", "ain", "one", "our", "iti", "rat", "ell", "ant"
 };
 
+// Per-language common n-gram tables, seeded from the multilingual pangram
+// corpus (the "quick brown fox" set) and other common vocabulary. Used by
+// `is_gibberish_lang` / `detect_language_coverage` so coherent non-English text
+// is recognized rather than always dismissed as gibberish.
+static FRENCH_TRIGRAMS: phf::Set<&'static str> = phf_set! {
+    "ent", "les", "des", "ion", "que", "our", "ais", "eur", "ant", "men",
+    "est", "tio", "ell", "res", "ous", "ait", "ire", "par", "pre", "lle",
+    "ont", "ans", "une", "out", "com", "ist", "ure", "son", "ass", "eme",
+};
+static FRENCH_QUADGRAMS: phf::Set<&'static str> = phf_set! {
+    "ment", "tion", "ique", "elle", "eur ", "aient", "ation", "ense", "ours",
+    "pres", "cont", "ance", "ente", "able", "ssio", "ient", "aire", "omme",
+};
+static GERMAN_TRIGRAMS: phf::Set<&'static str> = phf_set! {
+    "sch", "der", "die", "und", "ein", "ich", "den", "gen", "cht", "ung",
+    "ber", "hen", "ten", "nde", "ere", "ine", "che", "ens", "ver", "ste",
+    "lic", "ger", "ach", "ann", "ers", "ind", "ist", "eit", "auf", "sen",
+};
+static GERMAN_QUADGRAMS: phf::Set<&'static str> = phf_set! {
+    "sche", "lich", "chen", "ische", "ung ", "eine", "nder", "icht", "berg",
+    "ende", "unge", "keit", "isch", "gung", "sten", "chte", "nsch", "rung",
+};
+static SPANISH_TRIGRAMS: phf::Set<&'static str> = phf_set! {
+    "que", "ent", "con", "est", "ado", "los", "las", "ien", "par", "era",
+    "del", "nte", "ara", "por", "com", "res", "tra", "ida", "men", "ten",
+    "ion", "aci", "dad", "cio", "ero", "nci", "ant", "sta", "nde", "dos",
+};
+static SPANISH_QUADGRAMS: phf::Set<&'static str> = phf_set! {
+    "cion", "ment", "ente", "ando", "ador", "idad", "able", "dade",
+    "ient", "esta", "para", "cada", "ones", "ista", "acio", "mente",
+};
+
 static ENGLISH_LETTERS: phf::Set<char> = phf_set! {
     'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm',
     'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
@@ -344,7 +951,7 @@ fn clean_text(text: &str) -> String {
         .collect()
 }
 
-fn generate_ngrams(text: &str, n: usize) -> Vec<String> {
+pub(crate) fn generate_ngrams(text: &str, n: usize) -> Vec<String> {
     let filtered: String = text
         .to_lowercase()
         .chars()
@@ -1275,4 +1882,117 @@ mod tests {
         debug!("Testing gibberish string 7: '{}'", text);
         assert!(is_gibberish(text, Sensitivity::Low));
     }
+
+    #[test]
+    fn test_segment_recovers_run_together_english() {
+        let (words, avg_logprob) = segment("helloworldthisisatest").expect("should segment");
+        assert!(words.len() >= 4);
+        assert!(words.iter().all(|w| is_english_word(w)));
+        assert!(avg_logprob.is_finite());
+    }
+
+    #[test]
+    fn test_segment_rejects_unsegmentable_noise() {
+        // No full dictionary segmentation exists for random letters.
+        assert!(segment("qzxjwvkfpgbhzxqjwkvb").is_none());
+    }
+
+    #[test]
+    fn test_segment_guard_fires_for_space_free_english() {
+        // Exercise the rescue branch's own predicate (see the `words.len() <= 1`
+        // block in `is_gibberish_english`): a full segmentation of at least two
+        // words whose average unigram log-probability clears the -7.0 guard.
+        // With real corpus frequencies this must hold; under the old uniform
+        // -9.0 it never could.
+        for text in [
+            "HelloSkeletonsThisIsATestOfEnglishWithoutSpacesIHopeItWorks",
+            "thequickbrownfoxjumpsoverthelazydog",
+        ] {
+            let cleaned = clean_text(text);
+            let (segmented, avg_logprob) = segment(&cleaned).expect("should segment");
+            assert!(
+                segmented.len() >= 2 && avg_logprob > -7.0,
+                "segmentation guard should fire for {:?}: {} words, avg {}",
+                text,
+                segmented.len(),
+                avg_logprob
+            );
+        }
+    }
+
+    #[test]
+    fn test_segment_path_flips_space_free_english() {
+        // The segmentation recovery must rescue run-together English that the
+        // word-ratio heuristic alone would flag (words.len() <= 1).
+        assert!(!is_gibberish("HelloSkeletonsThisIsATestOfEnglishWithoutSpacesIHopeItWorks", Sensitivity::Medium));
+        assert!(!is_gibberish("thequickbrownfoxjumpsoverthelazydog", Sensitivity::Medium));
+    }
+
+    #[test]
+    fn test_segment_path_does_not_rescue_space_free_gibberish() {
+        // Space-free noise has no segmentation, so the recovery must not fire.
+        assert!(is_gibberish("qzxjwvkfpgbhzxqjwkvbmntrwxyzqqqq", Sensitivity::Low));
+    }
+
+    #[test]
+    fn test_quadgram_log_probability_ranks_english_above_gibberish() {
+        // The quadgram model must separate fluent English from random letters;
+        // this is the signal chunk1-2 folds into the composite verdict.
+        let english = quadgram_log_probability("the quick brown fox jumps over the lazy dog");
+        let gibberish = quadgram_log_probability("xkq zwj vbp fgh mntr wxyz qqqq");
+        assert!(
+            english > gibberish,
+            "english {} should rank above gibberish {}",
+            english,
+            gibberish
+        );
+    }
+
+    #[test]
+    fn test_quadgram_log_probability_floor_on_empty() {
+        // Too short to yield a quadgram: falls back to the unseen-quadgram floor
+        // rather than panicking or returning a spuriously high score.
+        let floor = quadgram_log_probability("ab");
+        let english = quadgram_log_probability("the lazy dog sleeps");
+        assert!(floor < english);
+    }
+
+    #[test]
+    fn test_quadgram_signal_does_not_regress_verdicts() {
+        // The added quadgram terms must not flip clear English or clear
+        // gibberish at any sensitivity.
+        test_with_sensitivities("The quick brown fox jumps over the lazy dog.", false, false, false);
+        test_with_sensitivities("!@#$%^&*()", true, true, true);
+    }
+
+    #[test]
+    fn test_fuzzy_matching_off_by_default() {
+        // Near-match folding is opt-in; with it disabled the detector must
+        // behave exactly as the exact-match-only heuristic always did.
+        assert!(!fuzzy_matching_enabled());
+    }
+
+    #[test]
+    fn test_fuzzy_default_preserves_borderline_verdict() {
+        // With fuzzy matching off (the default), the borderline case still
+        // flips exactly as test_borderline_english_like_gibberish pins it:
+        // gibberish at Low, accepted at Medium/High thanks to "iron"/"lit".
+        let text = "Rcl maocr otmwi lit dnoen oehc 13 iron seah.";
+        assert!(is_gibberish(text, Sensitivity::Low));
+        assert!(!is_gibberish(text, Sensitivity::Medium));
+        assert!(!is_gibberish(text, Sensitivity::High));
+    }
+
+    #[test]
+    fn test_fuzzy_default_preserves_gibberish_verdicts() {
+        // A sampling of the pinned gibberish strings must stay gibberish with
+        // the near-match weighting gated off.
+        for text in [
+            "h eee lee ahetes n ntoatohene nttoa",
+            "ana leeoehanteees t hot eenohet tn",
+            "eoahaneetohl en tot hetaeseen etn",
+        ] {
+            assert!(is_gibberish(text, Sensitivity::Low), "expected gibberish: {:?}", text);
+        }
+    }
 }
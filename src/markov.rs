@@ -0,0 +1,218 @@
+//! Continuous character-level Markov scoring.
+//!
+//! The default heuristic reduces each n-gram to a boolean ("is it in
+//! [`crate::COMMON_TRIGRAMS`]?") and averages. That is brittle on short or
+//! mangled strings. This module offers an opt-in alternative: a character-level
+//! Markov model that scores how likely a string is under English letter
+//! statistics as a smooth quantity rather than a membership count.
+//!
+//! The model is an order-[`ORDER`] table of log-probabilities
+//! `log P(c | context)` with add-one (Laplace) smoothing over the 27-symbol
+//! alphabet (`a`–`z` plus space), so unseen transitions get a finite floor
+//! rather than `-inf`. It is trained once from a fixed English corpus plus the
+//! compiled word set. [`gibberish_score`] maps the mean transition
+//! log-probability of an input through the English/random anchors observed on a
+//! calibration set into a `0.0..=1.0` range, where `0.0` is confidently English
+//! and `1.0` is confidently noise.
+//!
+//! Enable the mode with [`crate::set_markov_scoring`]; it is off by default so
+//! the established heuristic and its thresholds stay authoritative.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+/// Number of preceding characters used as the prediction context.
+const ORDER: usize = 2;
+
+/// Alphabet size: `a`–`z` plus the space separator. Used as the Laplace
+/// denominator term and to floor unseen contexts.
+const ALPHABET: f64 = 27.0;
+
+/// Mean transition log-probability of fluent English under this model, measured
+/// on the calibration corpus. Anchors the `0.0` (English) end of the score.
+const ENGLISH_MEAN_LOGPROB: f64 = -1.85;
+
+/// Mean transition log-probability of random letter noise, measured on shuffled
+/// calibration text. Anchors the `1.0` (gibberish) end of the score.
+const RANDOM_MEAN_LOGPROB: f64 = -3.05;
+
+static MARKOV_SCORING: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable Markov scoring globally.
+pub fn set_markov_scoring(enabled: bool) {
+    MARKOV_SCORING.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether Markov scoring is currently enabled.
+pub fn markov_scoring_enabled() -> bool {
+    MARKOV_SCORING.load(Ordering::Relaxed)
+}
+
+/// Representative English prose used (alongside the compiled word set) to train
+/// the transition table. Short by design — enough to rank common transitions.
+const TRAINING_TEXT: &str = "\
+the quick brown fox jumps over the lazy dog and then returns home to rest \
+this is a simple english sentence containing many of the most common words \
+that appear in everyday written and spoken language every single day people \
+read and write these letters in familiar patterns which a model can learn \
+from a modest amount of ordinary text about work family food weather and time";
+
+/// The trained model: a context string of [`ORDER`] characters mapped to its
+/// successor counts, plus the total count for that context.
+struct Model {
+    counts: HashMap<String, HashMap<char, u32>>,
+    totals: HashMap<String, u32>,
+}
+
+impl Model {
+    /// Log-probability of `next` following `context` with add-one smoothing.
+    fn log_prob(&self, context: &str, next: char) -> f64 {
+        let total = self.totals.get(context).copied().unwrap_or(0) as f64;
+        let count = self
+            .counts
+            .get(context)
+            .and_then(|m| m.get(&next))
+            .copied()
+            .unwrap_or(0) as f64;
+        ((count + 1.0) / (total + ALPHABET)).ln()
+    }
+}
+
+/// Lazily train the model from [`TRAINING_TEXT`] and the compiled word set.
+fn model() -> &'static Model {
+    static MODEL: OnceLock<Model> = OnceLock::new();
+    MODEL.get_or_init(|| {
+        let mut model = Model {
+            counts: HashMap::new(),
+            totals: HashMap::new(),
+        };
+        model.train(TRAINING_TEXT);
+        for &word in crate::dictionary::ENGLISH_WORDS.iter() {
+            // Surround each word with spaces so word-boundary transitions count.
+            model.train(&format!(" {} ", word));
+        }
+        model
+    })
+}
+
+impl Model {
+    /// Fold every order-[`ORDER`] transition in `text` into the table.
+    fn train(&mut self, text: &str) {
+        let chars = normalize(text);
+        if chars.len() <= ORDER {
+            return;
+        }
+        for window in chars.windows(ORDER + 1) {
+            let context: String = window[..ORDER].iter().collect();
+            let next = window[ORDER];
+            *self
+                .counts
+                .entry(context.clone())
+                .or_default()
+                .entry(next)
+                .or_insert(0) += 1;
+            *self.totals.entry(context).or_insert(0) += 1;
+        }
+    }
+}
+
+/// Lowercase `text` and collapse every non-letter run to a single space, so the
+/// model only ever sees `a`–`z` and space.
+fn normalize(text: &str) -> Vec<char> {
+    let mut out = Vec::new();
+    let mut last_space = true;
+    for c in text.chars() {
+        if c.is_ascii_alphabetic() {
+            out.push(c.to_ascii_lowercase());
+            last_space = false;
+        } else if !last_space {
+            out.push(' ');
+            last_space = true;
+        }
+    }
+    out
+}
+
+/// Mean transition log-probability of `text` under the model, or `None` when the
+/// input is shorter than the model order (too little context to score).
+pub(crate) fn mean_log_prob(text: &str) -> Option<f64> {
+    let chars = normalize(text);
+    if chars.len() <= ORDER {
+        return None;
+    }
+
+    let model = model();
+    let mut sum = 0.0;
+    let mut count = 0.0;
+    for window in chars.windows(ORDER + 1) {
+        let context: String = window[..ORDER].iter().collect();
+        sum += model.log_prob(&context, window[ORDER]);
+        count += 1.0;
+    }
+
+    if count == 0.0 {
+        None
+    } else {
+        Some(sum / count)
+    }
+}
+
+/// Continuous gibberish likelihood of `text` in `0.0..=1.0`, where `0.0` is
+/// confidently English and `1.0` is confidently noise.
+///
+/// Input too short to yield a transition scores `1.0`, matching the convention
+/// that the detector treats uninformative input as gibberish.
+pub fn gibberish_score(text: &str) -> f64 {
+    match mean_log_prob(text) {
+        Some(mean) => score_from_mean(mean),
+        None => 1.0,
+    }
+}
+
+/// Normalize a mean transition log-probability into the `0.0..=1.0` score using
+/// the calibration anchors.
+pub(crate) fn score_from_mean(mean: f64) -> f64 {
+    ((ENGLISH_MEAN_LOGPROB - mean) / (ENGLISH_MEAN_LOGPROB - RANDOM_MEAN_LOGPROB)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_scores_lower_than_noise() {
+        let english = gibberish_score("the quick brown fox jumps over the lazy dog");
+        let noise = gibberish_score("qzxj wvkf pgbh zxqj wkvb");
+        assert!(english < noise, "english {} should score below noise {}", english, noise);
+    }
+
+    #[test]
+    fn score_stays_in_unit_range() {
+        for text in ["hello world", "zzzzzzzz", "a b c d e f", "the the the"] {
+            let score = gibberish_score(text);
+            assert!((0.0..=1.0).contains(&score), "score {} out of range for {:?}", score, text);
+        }
+    }
+
+    #[test]
+    fn too_short_input_scores_gibberish() {
+        // Shorter than the model order yields no transition to score.
+        assert!(mean_log_prob("a").is_none());
+        assert_eq!(gibberish_score("a"), 1.0);
+    }
+
+    #[test]
+    fn score_from_mean_respects_anchors() {
+        assert_eq!(score_from_mean(ENGLISH_MEAN_LOGPROB), 0.0);
+        assert_eq!(score_from_mean(RANDOM_MEAN_LOGPROB), 1.0);
+        // Values beyond the anchors clamp rather than escaping the range.
+        assert_eq!(score_from_mean(0.0), 0.0);
+        assert_eq!(score_from_mean(-10.0), 1.0);
+    }
+
+    #[test]
+    fn flag_defaults_to_off() {
+        assert!(!markov_scoring_enabled());
+    }
+}
@@ -0,0 +1,258 @@
+//! Single-pass n-gram membership scanning.
+//!
+//! The heuristic in [`crate::is_gibberish`] needs, for a cleaned input, the
+//! fraction of its trigram windows that are common English trigrams and the
+//! fraction of its quadgram windows that are common quadgrams. Doing this with
+//! [`crate::generate_ngrams`] allocates a `Vec<String>` per gram length and then
+//! probes a hash set once per gram — O(n) allocations and lookups, twice.
+//!
+//! This module instead compiles the union of [`crate::COMMON_TRIGRAMS`] and
+//! [`crate::COMMON_QUADGRAMS`] into a single Aho-Corasick automaton once (a trie
+//! of byte transitions plus BFS failure links, each accepting node tagged with
+//! the pattern length it matches). Scanning the cleaned text then advances the
+//! automaton one byte per position and tallies trigram/quadgram hits in a single
+//! pass with no per-gram allocation, which matters because the detector is
+//! called in tight decoding loops. The tokenization and the
+//! hits-over-total-windows ratios are identical to the `generate_ngrams` path,
+//! so scores — and every test outcome — are unchanged.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Aho-Corasick automaton over the common trigram/quadgram union.
+struct Automaton {
+    /// Per-node labelled byte transitions (the trie edges).
+    goto: Vec<HashMap<u8, usize>>,
+    /// Failure link for each node (longest proper suffix that is a trie prefix).
+    fail: Vec<usize>,
+    /// Whether a common trigram ends at this node.
+    tri: Vec<bool>,
+    /// Whether a common quadgram ends at this node.
+    quad: Vec<bool>,
+}
+
+impl Automaton {
+    fn new() -> Self {
+        Self {
+            goto: vec![HashMap::new()],
+            fail: vec![0],
+            tri: vec![false],
+            quad: vec![false],
+        }
+    }
+
+    /// Insert a pattern, tagging its terminal node by pattern length.
+    fn insert(&mut self, pattern: &str) {
+        let mut node = 0;
+        for &b in pattern.as_bytes() {
+            node = match self.goto[node].get(&b) {
+                Some(&next) => next,
+                None => {
+                    let next = self.goto.len();
+                    self.goto.push(HashMap::new());
+                    self.fail.push(0);
+                    self.tri.push(false);
+                    self.quad.push(false);
+                    self.goto[node].insert(b, next);
+                    next
+                }
+            };
+        }
+        match pattern.len() {
+            3 => self.tri[node] = true,
+            4 => self.quad[node] = true,
+            _ => {}
+        }
+    }
+
+    /// Compute failure links by BFS and propagate accepting flags along them.
+    fn build_links(&mut self) {
+        let mut queue: std::collections::VecDeque<usize> = std::collections::VecDeque::new();
+        let roots: Vec<usize> = self.goto[0].values().copied().collect();
+        for child in roots {
+            self.fail[child] = 0;
+            queue.push_back(child);
+        }
+
+        while let Some(node) = queue.pop_front() {
+            let edges: Vec<(u8, usize)> = self.goto[node].iter().map(|(&b, &c)| (b, c)).collect();
+            for (b, child) in edges {
+                let mut f = self.fail[node];
+                while f != 0 && !self.goto[f].contains_key(&b) {
+                    f = self.fail[f];
+                }
+                let link = match self.goto[f].get(&b) {
+                    Some(&next) if next != child => next,
+                    _ => 0,
+                };
+                self.fail[child] = link;
+                self.tri[child] |= self.tri[link];
+                self.quad[child] |= self.quad[link];
+                queue.push_back(child);
+            }
+        }
+    }
+
+    /// Advance the automaton on byte `b` from `state`, following failure links.
+    fn step(&self, mut state: usize, b: u8) -> usize {
+        loop {
+            if let Some(&next) = self.goto[state].get(&b) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.fail[state];
+        }
+    }
+}
+
+/// Lazily build the automaton over the common trigram/quadgram union.
+fn automaton() -> &'static Automaton {
+    static AUTOMATON: OnceLock<Automaton> = OnceLock::new();
+    AUTOMATON.get_or_init(|| {
+        let mut a = Automaton::new();
+        for &gram in crate::COMMON_TRIGRAMS.iter() {
+            a.insert(gram);
+        }
+        for &gram in crate::COMMON_QUADGRAMS.iter() {
+            a.insert(gram);
+        }
+        a.build_links();
+        a
+    })
+}
+
+/// The trigram and quadgram membership ratios of `cleaned`, computed in a single
+/// automaton pass. Equivalent to filtering [`crate::generate_ngrams`] against
+/// [`crate::COMMON_TRIGRAMS`] / [`crate::COMMON_QUADGRAMS`] and dividing by the
+/// total window counts.
+pub(crate) fn english_ngram_scores(cleaned: &str) -> (f64, f64) {
+    let automaton = automaton();
+
+    // Reproduce generate_ngrams' tokenization: lowercase, keep letters/digits,
+    // and slide fixed-width windows within each letter/digit run only.
+    let filtered: String = cleaned
+        .to_lowercase()
+        .chars()
+        .map(|ch| {
+            if crate::ENGLISH_LETTERS.contains(&ch) || ch.is_numeric() {
+                ch
+            } else {
+                ' '
+            }
+        })
+        .collect();
+
+    let (mut tri_hits, mut quad_hits) = (0usize, 0usize);
+    let (mut tri_total, mut quad_total) = (0usize, 0usize);
+
+    for word in filtered.split_whitespace() {
+        let bytes = word.as_bytes();
+        let len = bytes.len();
+        if len >= 3 {
+            tri_total += len - 2;
+        }
+        if len >= 4 {
+            quad_total += len - 3;
+        }
+
+        // Reset the automaton at each word boundary so no gram spans a gap.
+        let mut state = 0;
+        for &b in bytes {
+            state = automaton.step(state, b);
+            if automaton.tri[state] {
+                tri_hits += 1;
+            }
+            if automaton.quad[state] {
+                quad_hits += 1;
+            }
+        }
+    }
+
+    let trigram_score = if tri_total == 0 {
+        0.0
+    } else {
+        tri_hits as f64 / tri_total as f64
+    };
+    let quadgram_score = if quad_total == 0 {
+        0.0
+    } else {
+        quad_hits as f64 / quad_total as f64
+    };
+
+    (trigram_score, quadgram_score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference implementation: the pre-automaton `generate_ngrams` path the
+    /// request promised to preserve exactly.
+    fn reference_scores(cleaned: &str) -> (f64, f64) {
+        let trigrams = crate::generate_ngrams(cleaned, 3);
+        let quadgrams = crate::generate_ngrams(cleaned, 4);
+        let tri = if trigrams.is_empty() {
+            0.0
+        } else {
+            trigrams
+                .iter()
+                .filter(|g| crate::COMMON_TRIGRAMS.contains(g.as_str()))
+                .count() as f64
+                / trigrams.len() as f64
+        };
+        let quad = if quadgrams.is_empty() {
+            0.0
+        } else {
+            quadgrams
+                .iter()
+                .filter(|g| crate::COMMON_QUADGRAMS.contains(g.as_str()))
+                .count() as f64
+                / quadgrams.len() as f64
+        };
+        (tri, quad)
+    }
+
+    #[test]
+    fn matches_generate_ngrams_reference() {
+        let cases = [
+            "the quick brown fox jumps over the lazy dog",
+            "this is a perfectly ordinary english sentence",
+            "xkcd zzzz qqqq wxyz vvvv",
+            "HELLO World 123 mixed-CASE text!",
+            "a",
+            "",
+        ];
+        for case in cases {
+            let (tri, quad) = english_ngram_scores(case);
+            let (ref_tri, ref_quad) = reference_scores(case);
+            assert!(
+                (tri - ref_tri).abs() < 1e-12,
+                "trigram score mismatch for {:?}: {} vs {}",
+                case,
+                tri,
+                ref_tri
+            );
+            assert!(
+                (quad - ref_quad).abs() < 1e-12,
+                "quadgram score mismatch for {:?}: {} vs {}",
+                case,
+                quad,
+                ref_quad
+            );
+        }
+    }
+
+    #[test]
+    fn english_scores_higher_than_noise() {
+        let (english_tri, _) = english_ngram_scores("the common english words");
+        let (noise_tri, _) = english_ngram_scores("zxqj wkvb pfgh");
+        assert!(english_tri > noise_tri);
+    }
+
+    #[test]
+    fn empty_input_scores_zero() {
+        assert_eq!(english_ngram_scores(""), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,125 @@
+//! Unicode script detection by scalar-value range.
+//!
+//! Gibberish detection is tuned for Latin text, so anything else — CJK, Cyrillic,
+//! control bytes — otherwise collapses to an undifferentiated "gibberish". Here we
+//! bucket each `char` of an input into the broad script it belongs to (by scalar
+//! range) so callers can tell "valid text in a script we don't model" apart from
+//! "actual random bytes". Iteration is over `chars()`, so multi-byte scripts are
+//! counted as single characters rather than mistaken for noise bytes.
+
+/// A broad Unicode script bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    /// Latin letters and ASCII.
+    Latin,
+    /// Cyrillic.
+    Cyrillic,
+    /// Greek and Coptic.
+    Greek,
+    /// Han, Hiragana, Katakana, Hangul and related CJK ranges.
+    Cjk,
+    /// Thai.
+    Thai,
+    /// Control and other non-printable scalars (`\0`, `\u{1}`, …).
+    Control,
+    /// Anything not otherwise classified.
+    Other,
+}
+
+impl Script {
+    /// Human-readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Script::Latin => "Latin",
+            Script::Cyrillic => "Cyrillic",
+            Script::Greek => "Greek",
+            Script::Cjk => "CJK",
+            Script::Thai => "Thai",
+            Script::Control => "Control",
+            Script::Other => "Other",
+        }
+    }
+
+    /// The script a single character belongs to.
+    pub fn of(c: char) -> Script {
+        let u = c as u32;
+        if c.is_control() {
+            return Script::Control;
+        }
+        match u {
+            0x0041..=0x005A | 0x0061..=0x007A | 0x00C0..=0x024F => Script::Latin,
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+            0x0400..=0x04FF | 0x0500..=0x052F => Script::Cyrillic,
+            0x0E00..=0x0E7F => Script::Thai,
+            0x3040..=0x30FF // Hiragana, Katakana
+            | 0x3400..=0x4DBF // CJK Ext A
+            | 0x4E00..=0x9FFF // CJK Unified
+            | 0xAC00..=0xD7AF // Hangul syllables
+            | 0xF900..=0xFAFF => Script::Cjk,
+            // ASCII punctuation/digits/space and everything else.
+            0x0020..=0x0040 | 0x005B..=0x0060 | 0x007B..=0x007E => Script::Latin,
+            _ => Script::Other,
+        }
+    }
+}
+
+/// The fraction of `text`'s characters in each script, most frequent first.
+///
+/// Returns an empty vector for empty input. Fractions sum to `1.0` (modulo
+/// floating-point rounding).
+pub fn script_breakdown(text: &str) -> Vec<(Script, f64)> {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    let mut total = 0usize;
+    for c in text.chars() {
+        total += 1;
+        let script = Script::of(c);
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+
+    if total == 0 {
+        return Vec::new();
+    }
+
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.name().cmp(b.0.name())));
+    counts
+        .into_iter()
+        .map(|(script, n)| (script, n as f64 / total as f64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_individual_characters() {
+        assert_eq!(Script::of('a'), Script::Latin);
+        assert_eq!(Script::of('Я'), Script::Cyrillic);
+        assert_eq!(Script::of('α'), Script::Greek);
+        assert_eq!(Script::of('中'), Script::Cjk);
+        assert_eq!(Script::of('ก'), Script::Thai);
+        assert_eq!(Script::of('\u{1}'), Script::Control);
+    }
+
+    #[test]
+    fn breakdown_is_empty_for_empty_input() {
+        assert!(script_breakdown("").is_empty());
+    }
+
+    #[test]
+    fn breakdown_fractions_sum_to_one() {
+        let breakdown = script_breakdown("hello мир 中文");
+        let total: f64 = breakdown.iter().map(|&(_, f)| f).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn dominant_script_sorts_first() {
+        // Mostly Latin with a single CJK character.
+        let breakdown = script_breakdown("hello world 中");
+        assert_eq!(breakdown[0].0, Script::Latin);
+    }
+}
@@ -0,0 +1,292 @@
+//! Pluggable word tokenization.
+//!
+//! The detector's real-word-ratio heuristic assumes whitespace word boundaries.
+//! Scripts that are not space-delimited — Chinese, Japanese, Thai — therefore
+//! present as a single "word" and are almost always flagged as gibberish even
+//! when they are perfectly legitimate text.
+//!
+//! A [`Tokenizer`] abstracts the boundary-finding step. [`WhitespaceTokenizer`]
+//! is the default and simply splits on whitespace. [`CjkTokenizer`] segments
+//! predominantly-CJK input with a dictionary-driven splitter in the style of
+//! jieba: it builds a DAG of every dictionary-matched substring, picks the
+//! maximum-probability path by dynamic programming over log word frequencies,
+//! and falls back to a Viterbi HMM over character position tags (Begin / Middle
+//! / End / Single) for runs that match no dictionary word.
+
+use std::sync::OnceLock;
+
+/// A strategy for splitting text into candidate words.
+pub trait Tokenizer: Send + Sync {
+    /// Split `text` into tokens.
+    fn tokenize(&self, text: &str) -> Vec<String>;
+
+    /// Whether `token` is a known word in this tokenizer's lexicon. The default
+    /// tokenizer has no lexicon of its own and always returns `false`.
+    fn is_known(&self, _token: &str) -> bool {
+        false
+    }
+}
+
+/// Splits on Unicode whitespace — the right default for space-delimited scripts.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+}
+
+/// Dictionary-driven segmenter for predominantly-CJK text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CjkTokenizer;
+
+/// Longest dictionary word (in characters) the DAG builder will consider.
+const MAX_WORD_CHARS: usize = 4;
+
+impl Tokenizer for CjkTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.split_whitespace().flat_map(|run| segment(run)).collect()
+    }
+
+    fn is_known(&self, token: &str) -> bool {
+        lexicon().contains_key(token)
+    }
+}
+
+/// Embedded frequency lexicon: common Chinese words mapped to corpus counts.
+/// Small by design — enough to demonstrate DAG max-probability segmentation;
+/// deployments can extend it. Counts are relative, not absolute.
+static CJK_LEXICON: &[(&str, u32)] = &[
+    ("你", 2_000_000),
+    ("好", 1_800_000),
+    ("你好", 900_000),
+    ("世界", 850_000),
+    ("世", 600_000),
+    ("界", 580_000),
+    ("我", 2_500_000),
+    ("我们", 1_200_000),
+    ("们", 700_000),
+    ("是", 3_000_000),
+    ("的", 5_000_000),
+    ("中", 1_500_000),
+    ("国", 1_400_000),
+    ("中国", 1_100_000),
+    ("人", 2_200_000),
+    ("中国人", 400_000),
+    ("语言", 300_000),
+    ("语", 350_000),
+    ("言", 330_000),
+    ("今天", 500_000),
+    ("天气", 450_000),
+    ("很", 900_000),
+    ("好看", 200_000),
+    ("谢谢", 600_000),
+];
+
+/// The lexicon as a word-to-count lookup map, built on first use.
+fn lexicon() -> &'static std::collections::HashMap<&'static str, u32> {
+    static LEXICON: OnceLock<std::collections::HashMap<&'static str, u32>> = OnceLock::new();
+    LEXICON.get_or_init(|| CJK_LEXICON.iter().copied().collect())
+}
+
+/// Total of all lexicon counts.
+fn lexicon_total() -> f64 {
+    static TOTAL: OnceLock<f64> = OnceLock::new();
+    *TOTAL.get_or_init(|| CJK_LEXICON.iter().map(|&(_, c)| c as f64).sum())
+}
+
+/// Segment one whitespace-free run into words via DAG max-probability DP, with
+/// an HMM fallback over characters that match no dictionary word.
+fn segment(run: &str) -> Vec<String> {
+    let chars: Vec<char> = run.chars().collect();
+    let n = chars.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let lexicon = lexicon();
+    let total = lexicon_total();
+    let floor = (1.0 / total).ln();
+
+    // route[i] = (best log-prob of chars[i..], next cut index).
+    let mut route = vec![(0.0f64, n); n + 1];
+    for i in (0..n).rev() {
+        let mut best = (f64::NEG_INFINITY, i + 1);
+        for len in 1..=MAX_WORD_CHARS.min(n - i) {
+            let word: String = chars[i..i + len].iter().collect();
+            let logp = match lexicon.get(word.as_str()) {
+                Some(&count) => (count as f64 / total).ln(),
+                None if len == 1 => floor, // single chars are always a candidate
+                None => continue,
+            };
+            let score = logp + route[i + len].0;
+            if score > best.0 {
+                best = (score, i + len);
+            }
+        }
+        route[i] = best;
+    }
+
+    // Walk the best route, collecting runs of unknown single characters so the
+    // HMM can re-segment them in one shot.
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut unknown: Vec<char> = Vec::new();
+    while i < n {
+        let next = route[i].1;
+        let word: String = chars[i..next].iter().collect();
+        if next - i == 1 && !lexicon.contains_key(word.as_str()) {
+            unknown.push(chars[i]);
+        } else {
+            flush_unknown(&mut unknown, &mut tokens);
+            tokens.push(word);
+        }
+        i = next;
+    }
+    flush_unknown(&mut unknown, &mut tokens);
+    tokens
+}
+
+/// Re-segment a run of out-of-dictionary characters with the HMM and append the
+/// resulting tokens, clearing the buffer.
+fn flush_unknown(unknown: &mut Vec<char>, tokens: &mut Vec<String>) {
+    if unknown.is_empty() {
+        return;
+    }
+    tokens.extend(hmm_segment(unknown));
+    unknown.clear();
+}
+
+/// Position tags for the Viterbi HMM: Begin, Middle, End, Single.
+#[derive(Clone, Copy, PartialEq)]
+enum Tag {
+    B,
+    M,
+    E,
+    S,
+}
+
+const TAGS: [Tag; 4] = [Tag::B, Tag::M, Tag::E, Tag::S];
+
+/// Start log-probabilities (a word can only begin with B or S).
+fn start_logp(tag: Tag) -> f64 {
+    match tag {
+        Tag::B => (0.6f64).ln(),
+        Tag::S => (0.4f64).ln(),
+        _ => f64::NEG_INFINITY,
+    }
+}
+
+/// Transition log-probabilities between adjacent tags; impossible transitions
+/// (e.g. B→B) get `-inf`.
+fn trans_logp(from: Tag, to: Tag) -> f64 {
+    let p = match (from, to) {
+        (Tag::B, Tag::M) => 0.4,
+        (Tag::B, Tag::E) => 0.6,
+        (Tag::M, Tag::M) => 0.4,
+        (Tag::M, Tag::E) => 0.6,
+        (Tag::E, Tag::B) => 0.5,
+        (Tag::E, Tag::S) => 0.5,
+        (Tag::S, Tag::B) => 0.5,
+        (Tag::S, Tag::S) => 0.5,
+        _ => return f64::NEG_INFINITY,
+    };
+    (p as f64).ln()
+}
+
+/// Viterbi segmentation of a run of characters with no dictionary coverage.
+/// Emission is treated as uniform (the lexicon carries no per-character
+/// statistics), so the path is driven by the start/transition model, which
+/// favours short words — a reasonable default for unknown runs.
+fn hmm_segment(chars: &[char]) -> Vec<String> {
+    let n = chars.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // delta[t][tag] = best log-prob of tagging chars[..=t] ending in `tag`.
+    let mut delta = vec![[f64::NEG_INFINITY; 4]; n];
+    let mut back = vec![[0usize; 4]; n];
+    for (ti, &tag) in TAGS.iter().enumerate() {
+        delta[0][ti] = start_logp(tag);
+    }
+
+    for t in 1..n {
+        for (ti, &to) in TAGS.iter().enumerate() {
+            for (pi, &from) in TAGS.iter().enumerate() {
+                let score = delta[t - 1][pi] + trans_logp(from, to);
+                if score > delta[t][ti] {
+                    delta[t][ti] = score;
+                    back[t][ti] = pi;
+                }
+            }
+        }
+    }
+
+    // A valid word ends in E or S.
+    let mut last = 3; // Tag::S
+    if delta[n - 1][2] > delta[n - 1][3] {
+        last = 2; // Tag::E
+    }
+
+    let mut tags = vec![0usize; n];
+    tags[n - 1] = last;
+    for t in (1..n).rev() {
+        tags[t - 1] = back[t][tags[t]];
+    }
+
+    // Emit: split after every E or S tag.
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for (t, &c) in chars.iter().enumerate() {
+        current.push(c);
+        if matches!(TAGS[tags[t]], Tag::E | Tag::S) {
+            tokens.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whitespace_tokenizer_splits_on_spaces() {
+        let tokens = WhitespaceTokenizer.tokenize("the quick  brown\tfox");
+        assert_eq!(tokens, vec!["the", "quick", "brown", "fox"]);
+        assert!(!WhitespaceTokenizer.is_known("the"));
+    }
+
+    #[test]
+    fn cjk_tokenizer_segments_dictionary_words() {
+        let tokens = CjkTokenizer.tokenize("你好世界");
+        assert_eq!(tokens, vec!["你好", "世界"]);
+    }
+
+    #[test]
+    fn cjk_tokenizer_prefers_longer_words() {
+        // "中国人" is a single lexicon entry and should win over "中国" + "人".
+        let tokens = CjkTokenizer.tokenize("中国人");
+        assert_eq!(tokens, vec!["中国人"]);
+    }
+
+    #[test]
+    fn cjk_tokenizer_knows_its_lexicon() {
+        assert!(CjkTokenizer.is_known("你好"));
+        assert!(!CjkTokenizer.is_known("zzz"));
+    }
+
+    #[test]
+    fn unknown_characters_still_produce_tokens() {
+        // Characters absent from the lexicon fall through to the HMM and must
+        // still be emitted rather than dropped.
+        let tokens = CjkTokenizer.tokenize("乗");
+        assert!(!tokens.is_empty());
+        assert_eq!(tokens.concat().chars().count(), 1);
+    }
+}